@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+
+use crate::players::{BonusStatus, Category, Dice, Scoreboard, BONUS_LIMIT, BONUS_SCORE};
+
+pub const MAX_REROLLS: u8 = 2;
+
+type Counts = [u8; 6];
+
+/// Roughly what each category scores on average over a well played game.
+/// Used as the cost of filling a category, so that good boxes are not wasted early.
+const TYPICAL_SCORES: [f64; 12] = [
+    2.1, 5.3, 8.6, 12.2, 15.7, 19.2, 22.0, 10.0, 13.0, 13.0, 20.0, 16.0,
+];
+
+pub struct Hint {
+    /// Dice (by position) to keep for the next reroll
+    pub hold: [bool; 5],
+    /// Expected value of the turn when holding `hold` and playing on optimally.
+    /// A rating rather than points: Ones ~ Sixes also count their bonus share.
+    pub expected: f64,
+    /// Best category for the dice as they are right now
+    pub category: Category,
+    pub score_now: u16,
+    /// Whether scoring now is at least as good as rerolling anything
+    pub should_stop: bool,
+}
+
+/// Evaluates a roll against the open categories of one scoreboard.
+///
+/// A final hand is worth the best category score it can take. While the
+/// upper-section bonus is still reachable, Ones ~ Sixes also earn their share
+/// of the bonus so that filling them well is not undervalued.
+pub struct Advisor<'a> {
+    scoreboard: &'a Scoreboard,
+    opportunity_cost: bool,
+    outcomes: Vec<Vec<(Counts, f64)>>,
+    hand_memo: HashMap<(Counts, u8), f64>,
+    keep_memo: HashMap<(Counts, u8), f64>,
+}
+
+impl<'a> Advisor<'a> {
+    pub fn new(scoreboard: &'a Scoreboard) -> Self {
+        Self {
+            scoreboard,
+            opportunity_cost: false,
+            outcomes: (0..=5).map(roll_outcomes).collect(),
+            hand_memo: HashMap::new(),
+            keep_memo: HashMap::new(),
+        }
+    }
+
+    /// Also charges each category its typical score, which steers the choice
+    /// towards what a whole game (not just this turn) gains from it.
+    pub fn strategic(scoreboard: &'a Scoreboard) -> Self {
+        Self {
+            opportunity_cost: true,
+            ..Self::new(scoreboard)
+        }
+    }
+
+    pub fn advise(&mut self, dice: Dice, rerolls: u8) -> Option<Hint> {
+        let (category, value_now) = self.best_category(&dice)?;
+        let score_now = category.score(&dice);
+
+        let mut best = ([true; 5], value_now);
+        for mask in 0..(1u8 << 5) {
+            let hold = std::array::from_fn(|i| mask & (1 << i) != 0);
+            if rerolls == 0 || hold == [true; 5] {
+                continue;
+            }
+
+            let mut keep = [0; 6];
+            for (&die, _) in dice.iter().zip(hold).filter(|(_, held)| *held) {
+                keep[die as usize - 1] += 1;
+            }
+
+            let expected = self.keep_value(keep, rerolls);
+            if expected > best.1 + f64::EPSILON {
+                best = (hold, expected);
+            }
+        }
+
+        Some(Hint {
+            hold: best.0,
+            expected: best.1,
+            category,
+            score_now,
+            should_stop: best.0 == [true; 5],
+        })
+    }
+
+    /// Best open category for a final hand, with its heuristic value.
+    pub fn best_category(&self, dice: &Dice) -> Option<(Category, f64)> {
+        Category::ALL
+            .into_iter()
+            .filter(|category| category.is_open(self.scoreboard))
+            .map(|category| (category, self.category_value(category, dice)))
+            .max_by(|lhs, rhs| lhs.1.total_cmp(&rhs.1))
+    }
+
+    fn category_value(&self, category: Category, dice: &Dice) -> f64 {
+        let score = category.score(dice) as f64;
+        let value = match category.face() {
+            Some(_) if self.scoreboard.bonus_status() == BonusStatus::Possible => {
+                score + BONUS_SCORE as f64 * score / BONUS_LIMIT as f64
+            }
+            _ => score,
+        };
+
+        if self.opportunity_cost {
+            let index = Category::ALL.iter().position(|c| *c == category);
+            value - index.map_or(0.0, |index| TYPICAL_SCORES[index])
+        } else {
+            value
+        }
+    }
+
+    fn hand_value(&mut self, hand: Counts, rerolls: u8) -> f64 {
+        if let Some(&value) = self.hand_memo.get(&(hand, rerolls)) {
+            return value;
+        }
+
+        let stop = self
+            .best_category(&counts_to_dice(hand))
+            .map_or(0.0, |(_, value)| value);
+        let value = if rerolls == 0 {
+            stop
+        } else {
+            sub_multisets(hand)
+                .into_iter()
+                .filter(|keep| *keep != hand)
+                .map(|keep| self.keep_value(keep, rerolls))
+                .fold(stop, f64::max)
+        };
+
+        self.hand_memo.insert((hand, rerolls), value);
+        value
+    }
+
+    fn keep_value(&mut self, keep: Counts, rerolls: u8) -> f64 {
+        if let Some(&value) = self.keep_memo.get(&(keep, rerolls)) {
+            return value;
+        }
+
+        let to_roll = 5 - keep.iter().sum::<u8>() as usize;
+        let value = self.outcomes[to_roll]
+            .clone()
+            .into_iter()
+            .map(|(outcome, probability)| {
+                let hand = std::array::from_fn(|i| keep[i] + outcome[i]);
+                probability * self.hand_value(hand, rerolls - 1)
+            })
+            .sum();
+
+        self.keep_memo.insert((keep, rerolls), value);
+        value
+    }
+}
+
+fn counts_to_dice(counts: Counts) -> Dice {
+    let mut dice = [1; 5];
+    let faces = counts
+        .iter()
+        .zip(1..)
+        .flat_map(|(&count, face)| std::iter::repeat_n(face, count as usize));
+    for (die, face) in dice.iter_mut().zip(faces) {
+        *die = face;
+    }
+    dice
+}
+
+fn sub_multisets(counts: Counts) -> Vec<Counts> {
+    let mut subsets = vec![[0; 6]];
+    for (i, &count) in counts.iter().enumerate() {
+        subsets = subsets
+            .into_iter()
+            .flat_map(|subset| {
+                (0..=count).map(move |c| {
+                    let mut subset = subset;
+                    subset[i] = c;
+                    subset
+                })
+            })
+            .collect();
+    }
+    subsets
+}
+
+/// Every distinct outcome of rolling `n` dice, with its probability.
+fn roll_outcomes(n: u8) -> Vec<(Counts, f64)> {
+    let factorial = |k: u8| (1..=k as u32).product::<u32>() as f64;
+    let total = 6f64.powi(n as i32);
+
+    sub_multisets([n; 6])
+        .into_iter()
+        .filter(|outcome| outcome.iter().sum::<u8>() == n)
+        .map(|outcome| {
+            let arrangements =
+                factorial(n) / outcome.iter().map(|&c| factorial(c)).product::<f64>();
+            (outcome, arrangements / total)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn holds_a_made_yacht() {
+        let scoreboard = Scoreboard::default();
+        let hint = Advisor::new(&scoreboard).advise([4; 5], 2).unwrap();
+
+        assert!(hint.should_stop);
+        assert_eq!(hint.hold, [true; 5]);
+        assert_eq!(hint.category, Category::Yacht);
+        assert_eq!(hint.score_now, 50);
+    }
+
+    #[test]
+    fn stops_without_rerolls() {
+        let scoreboard = Scoreboard::default();
+        let hint = Advisor::new(&scoreboard)
+            .advise([1, 2, 3, 5, 6], 0)
+            .unwrap();
+
+        assert!(hint.should_stop);
+        assert_eq!(hint.hold, [true; 5]);
+    }
+
+    #[test]
+    fn rerolls_a_poor_hand() {
+        let scoreboard = Scoreboard::default();
+        let hint = Advisor::new(&scoreboard)
+            .advise([1, 2, 4, 6, 6], 2)
+            .unwrap();
+
+        assert!(!hint.should_stop);
+        assert!(hint.expected > f64::from(hint.score_now));
+    }
+}
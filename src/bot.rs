@@ -0,0 +1,188 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::advisor::{Advisor, MAX_REROLLS};
+use crate::players::{Category, Dice, Scoreboard};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Difficulty {
+    /// Keeps the most common face and takes the highest score on the table.
+    Greedy,
+    /// Maximizes the expected score of the current turn.
+    ExpectedValue,
+    /// Expected value minus what each category typically scores over a game, so
+    /// that good boxes are not used up early. A heuristic, not optimal play.
+    #[serde(alias = "Strategic")]
+    Heuristic,
+}
+
+impl Difficulty {
+    pub const ALL: [Self; 3] = [Self::Greedy, Self::ExpectedValue, Self::Heuristic];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Greedy => "Greedy",
+            Self::ExpectedValue => "Expected Value",
+            Self::Heuristic => "Heuristic",
+        }
+    }
+}
+
+/// A tiny xorshift generator, good enough for dice.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn from_time() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos() as u64)
+            .unwrap_or(0);
+        // xorshift must never be seeded with zero
+        Self(nanos | 1)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    #[inline]
+    pub fn roll_die(&mut self) -> u8 {
+        (self.next_u64() % 6) as u8 + 1
+    }
+
+    pub fn roll(&mut self, dice: &mut Dice, hold: [bool; 5]) {
+        for (die, held) in dice.iter_mut().zip(hold) {
+            if !held {
+                *die = self.roll_die();
+            }
+        }
+    }
+}
+
+pub struct Turn {
+    /// Every roll of the turn, the first one included
+    pub rolls: Vec<Dice>,
+    pub category: Category,
+}
+
+impl Turn {
+    #[inline]
+    pub fn dice(&self) -> &Dice {
+        self.rolls.last().expect("a turn has at least one roll")
+    }
+}
+
+/// Plays one whole turn for a bot. `None` if the scoreboard is already full.
+pub fn play_turn(scoreboard: &Scoreboard, difficulty: Difficulty, rng: &mut Rng) -> Option<Turn> {
+    let mut dice = [1; 5];
+    rng.roll(&mut dice, [false; 5]);
+    let mut rolls = vec![dice];
+
+    for rerolls in (1..=MAX_REROLLS).rev() {
+        let hold = choose_hold(scoreboard, difficulty, dice, rerolls)?;
+        if hold == [true; 5] {
+            break;
+        }
+        rng.roll(&mut dice, hold);
+        rolls.push(dice);
+    }
+
+    let category = choose_category(scoreboard, difficulty, &dice)?;
+    Some(Turn { rolls, category })
+}
+
+fn choose_hold(
+    scoreboard: &Scoreboard,
+    difficulty: Difficulty,
+    dice: Dice,
+    rerolls: u8,
+) -> Option<[bool; 5]> {
+    match difficulty {
+        Difficulty::Greedy => {
+            let face = (1..=6)
+                .max_by_key(|face| (dice.iter().filter(|die| *die == face).count(), *face))?;
+            Some(dice.map(|die| die == face))
+        }
+        Difficulty::ExpectedValue => Advisor::new(scoreboard)
+            .advise(dice, rerolls)
+            .map(|hint| hint.hold),
+        Difficulty::Heuristic => Advisor::strategic(scoreboard)
+            .advise(dice, rerolls)
+            .map(|hint| hint.hold),
+    }
+}
+
+fn choose_category(
+    scoreboard: &Scoreboard,
+    difficulty: Difficulty,
+    dice: &Dice,
+) -> Option<Category> {
+    match difficulty {
+        Difficulty::Greedy => Category::ALL
+            .into_iter()
+            .filter(|category| category.is_open(scoreboard))
+            .rev()
+            .max_by_key(|category| category.score(dice)),
+        Difficulty::ExpectedValue => Advisor::new(scoreboard)
+            .best_category(dice)
+            .map(|(category, _)| category),
+        Difficulty::Heuristic => Advisor::strategic(scoreboard)
+            .best_category(dice)
+            .map(|(category, _)| category),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns_score_in_an_open_category() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        for difficulty in Difficulty::ALL {
+            let mut scoreboard = Scoreboard {
+                choice: Some(20),
+                yacht: Some(0),
+                ..Scoreboard::default()
+            };
+            for _ in 0..4 {
+                let turn = play_turn(&scoreboard, difficulty, &mut rng).unwrap();
+                assert!(turn.category.is_open(&scoreboard), "{}", difficulty.name());
+                assert!(turn.rolls.len() <= 1 + MAX_REROLLS as usize);
+                scoreboard.full_house = Some(0);
+            }
+        }
+    }
+
+    #[test]
+    fn a_full_card_takes_no_turn() {
+        let scoreboard = Scoreboard {
+            numbers: [Some(0); 6],
+            choice: Some(5),
+            full_house: Some(0),
+            four_of_kind: Some(0),
+            small_straight: Some(0),
+            large_straight: Some(0),
+            yacht: Some(0),
+            ..Scoreboard::default()
+        };
+        let mut rng = Rng(1);
+        for difficulty in Difficulty::ALL {
+            assert!(play_turn(&scoreboard, difficulty, &mut rng).is_none());
+        }
+    }
+
+    #[test]
+    fn greedy_holds_the_most_common_face() {
+        let scoreboard = Scoreboard::default();
+        let hold = choose_hold(&scoreboard, Difficulty::Greedy, [2, 5, 2, 6, 2], 2).unwrap();
+        assert_eq!(hold, [true, false, true, false, true]);
+        // A tie keeps the higher face
+        let hold = choose_hold(&scoreboard, Difficulty::Greedy, [3, 4, 4, 3, 1], 2).unwrap();
+        assert_eq!(hold, [false, true, true, false, false]);
+    }
+}
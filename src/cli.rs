@@ -0,0 +1,146 @@
+use std::path::PathBuf;
+
+use crate::clock::{ClockMode, TimeoutAction};
+use crate::theme::ThemeName;
+
+pub const USAGE: &str = "\
+Usage: phasellus [OPTIONS]
+
+Options:
+    --practice          start a solo practice game
+    --host <PORT>       host a network game on PORT
+    --join <ADDRESS>    join the network game hosted at ADDRESS (e.g. 127.0.0.1:7878)
+    --name <NAME>       your player name in a network game
+    --watch <FILE>      follow a saved game file without playing
+    --spectate <ADDRESS>
+                        follow the network game hosted at ADDRESS without playing
+    --tournament <FILE> carry on with the tournament kept in FILE
+    --turn-timer <SECONDS>
+                        give every turn SECONDS to be played
+    --chess-clock <MINUTES>
+                        give every player MINUTES for the whole game
+    --on-timeout <ACTION>
+                        warn, scratch or log when someone runs out of time
+    --large             use wider columns, for a big screen
+    --ascii             draw the board with plain ASCII characters
+    --unicode           draw the board with box-drawing characters, even when the
+                        terminal does not seem to support them
+    --theme <THEME>     terminal, light, dark, high-contrast or colorblind
+    --overlay <DIR>     keep board.html and board.json in DIR up to date, for stream overlays
+    --http <PORT>       serve a JSON API on 127.0.0.1:PORT (needs the `http-api` feature)
+    -h, --help          show this message";
+
+#[derive(Default)]
+pub struct Options {
+    pub practice: bool,
+    pub host: Option<u16>,
+    pub join: Option<String>,
+    pub name: Option<String>,
+    pub watch: Option<PathBuf>,
+    pub spectate: Option<String>,
+    pub tournament: Option<PathBuf>,
+    /// The clock and its limit in seconds
+    pub clock: Option<(ClockMode, u64)>,
+    pub on_timeout: Option<TimeoutAction>,
+    pub large: bool,
+    pub theme: Option<ThemeName>,
+    /// `None` leaves it to the terminal
+    pub ascii: Option<bool>,
+    pub overlay: Option<PathBuf>,
+    pub http: Option<u16>,
+    pub help: bool,
+}
+
+impl Options {
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self::default();
+
+        while let Some(arg) = args.next() {
+            let mut value = |option: &str| {
+                args.next()
+                    .ok_or_else(|| format!("`{option}` needs a value"))
+            };
+
+            match arg.as_str() {
+                "--practice" => options.practice = true,
+                "--host" => options.host = Some(parse_port(value("--host")?)?),
+                "--join" => options.join = Some(value("--join")?),
+                "--name" => options.name = Some(value("--name")?),
+                "--watch" => options.watch = Some(PathBuf::from(value("--watch")?)),
+                "--spectate" => options.spectate = Some(value("--spectate")?),
+                "--tournament" => {
+                    options.tournament = Some(PathBuf::from(value("--tournament")?));
+                }
+                "--turn-timer" | "--chess-clock" => {
+                    if options.clock.is_some() {
+                        return Err("only one of `--turn-timer` and `--chess-clock` can be used"
+                            .to_string());
+                    }
+                    let amount = parse_amount(value(&arg)?)?;
+                    options.clock = Some(if arg == "--turn-timer" {
+                        (ClockMode::Turn, amount)
+                    } else {
+                        (ClockMode::Total, amount * 60)
+                    });
+                }
+                "--on-timeout" => {
+                    let action = value("--on-timeout")?;
+                    options.on_timeout = Some(
+                        TimeoutAction::parse(&action)
+                            .ok_or_else(|| format!("unknown timeout action `{action}`"))?,
+                    );
+                }
+                "--large" => options.large = true,
+                "--ascii" => options.ascii = Some(true),
+                "--unicode" => options.ascii = Some(false),
+                "--theme" => {
+                    let theme = value("--theme")?;
+                    options.theme = Some(
+                        ThemeName::parse(&theme)
+                            .ok_or_else(|| format!("unknown theme `{theme}`"))?,
+                    );
+                }
+                "--overlay" => options.overlay = Some(PathBuf::from(value("--overlay")?)),
+                "--http" => options.http = Some(parse_port(value("--http")?)?),
+                "-h" | "--help" => options.help = true,
+                _ => return Err(format!("unknown option `{arg}`")),
+            }
+        }
+
+        if options.http.is_some() && !cfg!(feature = "http-api") {
+            return Err("`--http` needs phasellus built with the `http-api` feature".to_string());
+        }
+        if options.join.is_some() && options.name.is_none() {
+            return Err("`--join` needs a `--name`".to_string());
+        }
+        let modes = [
+            options.practice,
+            options.host.is_some(),
+            options.join.is_some(),
+            options.watch.is_some(),
+            options.spectate.is_some(),
+            options.tournament.is_some(),
+        ];
+        if modes.iter().filter(|mode| **mode).count() > 1 {
+            return Err(
+                "only one of `--practice`, `--host`, `--join`, `--watch`, `--spectate` and `--tournament` can be used"
+                    .to_string(),
+            );
+        }
+
+        Ok(options)
+    }
+}
+
+fn parse_amount(amount: String) -> Result<u64, String> {
+    amount
+        .parse()
+        .ok()
+        .filter(|amount| *amount > 0)
+        .ok_or_else(|| format!("`{amount}` is not a positive number"))
+}
+
+fn parse_port(port: String) -> Result<u16, String> {
+    port.parse()
+        .map_err(|_| format!("`{port}` is not a valid port"))
+}
@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::players::Players;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ClockMode {
+    /// Every turn starts again from the full time.
+    Turn,
+    /// A chess clock: the time is for the whole game and runs only on one's own turns.
+    Total,
+}
+
+/// What happens to a player whose time runs out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TimeoutAction {
+    #[default]
+    Warn,
+    /// Scores zero in their first open category, which ends the turn.
+    Scratch,
+    Log,
+}
+
+impl TimeoutAction {
+    pub const ALL: [Self; 3] = [Self::Warn, Self::Scratch, Self::Log];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Warn => "warn",
+            Self::Scratch => "scratch",
+            Self::Log => "log",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.name() == name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ClockConfig {
+    pub mode: ClockMode,
+    pub seconds: u64,
+    #[serde(default)]
+    pub on_timeout: TimeoutAction,
+}
+
+/// The turn being timed.
+struct TurnTimer {
+    player: String,
+    /// Time of this turn up to `since`
+    used: Duration,
+    /// When the clock last started, `None` while paused
+    since: Option<Instant>,
+    timed_out: bool,
+}
+
+impl TurnTimer {
+    fn elapsed(&self) -> Duration {
+        self.used + self.since.map_or(Duration::ZERO, |since| since.elapsed())
+    }
+}
+
+/// Times the turns of a game. It waits, paused, at the start of every game.
+pub struct Clock {
+    pub config: ClockConfig,
+    /// Time each player used in the turns they finished
+    used: HashMap<String, Duration>,
+    turn: Option<TurnTimer>,
+    paused: bool,
+    /// Whether no score was entered yet, to tell when a new game starts
+    is_fresh: bool,
+}
+
+impl Clock {
+    pub fn new(config: ClockConfig) -> Self {
+        Self {
+            config,
+            used: HashMap::new(),
+            turn: None,
+            paused: true,
+            is_fresh: true,
+        }
+    }
+
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Stops or starts the clock of the player on turn.
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+        if let Some(turn) = &mut self.turn {
+            if self.paused {
+                turn.used = turn.elapsed();
+                turn.since = None;
+            } else {
+                turn.since = Some(Instant::now());
+            }
+        }
+    }
+
+    /// Ends the turn of whoever is no longer on turn, and starts over when the
+    /// scores are cleared for a new game.
+    pub fn follow(&mut self, players: &Players) {
+        let is_fresh = players
+            .iter()
+            .all(|(_, scoreboard)| scoreboard.filled_count() == 0);
+        if is_fresh && !self.is_fresh {
+            *self = Self::new(self.config);
+        }
+        self.is_fresh = is_fresh;
+
+        let current = players.current_player();
+        if self.turn.as_ref().map(|turn| &turn.player) == current {
+            return;
+        }
+        if let Some(turn) = self.turn.take() {
+            *self.used.entry(turn.player.clone()).or_default() += turn.elapsed();
+        }
+        self.turn = current.map(|player| TurnTimer {
+            player: player.clone(),
+            used: Duration::ZERO,
+            since: (!self.paused).then(Instant::now),
+            timed_out: false,
+        });
+    }
+
+    /// The time `name` has left on their turn, or for the rest of the game.
+    pub fn time_left(&self, name: &str) -> Duration {
+        let limit = Duration::from_secs(self.config.seconds);
+        let on_turn = self
+            .turn
+            .as_ref()
+            .filter(|turn| turn.player == name)
+            .map_or(Duration::ZERO, TurnTimer::elapsed);
+        let used = match self.config.mode {
+            ClockMode::Turn => on_turn,
+            ClockMode::Total => self.used.get(name).copied().unwrap_or_default() + on_turn,
+        };
+        limit.saturating_sub(used)
+    }
+
+    /// The player whose time just ran out, once per turn.
+    pub fn time_out(&mut self) -> Option<String> {
+        let time_left = self.time_left(&self.turn.as_ref()?.player);
+        let turn = self.turn.as_mut()?;
+        if turn.timed_out || !time_left.is_zero() {
+            return None;
+        }
+        turn.timed_out = true;
+        Some(turn.player.clone())
+    }
+
+    /// The widest the time left of a player can get on the board.
+    pub fn max_width(&self) -> usize {
+        format_time(Duration::from_secs(self.config.seconds)).len()
+    }
+}
+
+/// `m:ss`, or `h:mm:ss` from an hour on. Part of a second counts as a whole one.
+pub fn format_time(time: Duration) -> String {
+    let seconds = time.as_secs() + u64::from(time.subsec_nanos() > 0);
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::players::ScoreInput;
+
+    fn config(mode: ClockMode, seconds: u64) -> ClockConfig {
+        ClockConfig {
+            mode,
+            seconds,
+            on_timeout: TimeoutAction::Warn,
+        }
+    }
+
+    fn two_players() -> Players {
+        let mut players = Players::new();
+        players.add_player("A").unwrap();
+        players.add_player("B").unwrap();
+        players
+    }
+
+    #[test]
+    fn starts_paused_with_the_full_time() {
+        let mut clock = Clock::new(config(ClockMode::Total, 90));
+        clock.follow(&two_players());
+        assert!(clock.is_paused());
+        assert_eq!(clock.time_left("A"), Duration::from_secs(90));
+        assert_eq!(clock.time_out(), None);
+
+        clock.toggle_pause();
+        assert!(!clock.is_paused());
+    }
+
+    #[test]
+    fn times_out_once_per_turn_of_whoever_is_on_turn() {
+        let mut players = two_players();
+        let mut clock = Clock::new(config(ClockMode::Turn, 0));
+        clock.follow(&players);
+        assert_eq!(clock.time_out().as_deref(), Some("A"));
+        assert_eq!(clock.time_out(), None);
+
+        players.calculate_score("A", ScoreInput::Choice(Some(20)));
+        clock.follow(&players);
+        assert_eq!(clock.time_out().as_deref(), Some("B"));
+        // Following the same turn again does not start it over
+        clock.follow(&players);
+        assert_eq!(clock.time_out(), None);
+    }
+
+    #[test]
+    fn a_cleared_board_starts_the_clock_over() {
+        let mut players = two_players();
+        let mut clock = Clock::new(config(ClockMode::Total, 60));
+        clock.follow(&players);
+        clock.toggle_pause();
+        players.calculate_score("A", ScoreInput::Choice(Some(20)));
+        clock.follow(&players);
+
+        players.clear_score();
+        clock.follow(&players);
+        assert!(clock.is_paused());
+        assert_eq!(clock.time_left("A"), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn time_is_rounded_up_to_the_second() {
+        assert_eq!(format_time(Duration::ZERO), "0:00");
+        assert_eq!(format_time(Duration::from_millis(59_001)), "1:00");
+        assert_eq!(format_time(Duration::from_secs(605)), "10:05");
+        assert_eq!(format_time(Duration::from_secs(3_725)), "1:02:05");
+    }
+}
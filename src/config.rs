@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::clock::ClockConfig;
+use crate::keymap::{Action, Bindings};
+use crate::theme::ThemeName;
+
+const CONFIG_FILENAME: &str = "config.json";
+
+/// Preferences read from `config.json` in the config directory. Options on the
+/// command line win over it.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub theme: ThemeName,
+    /// Keys that replace the default ones of an action
+    pub keys: HashMap<Action, Bindings>,
+    /// A time limit on turns, off when missing
+    pub clock: Option<ClockConfig>,
+}
+
+impl Config {
+    /// A missing file is the default configuration.
+    pub fn load() -> Result<Self, String> {
+        let Some(path) = config_path() else {
+            return Ok(Self::default());
+        };
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(format!("{}: {err}", path.display())),
+        };
+
+        serde_json::from_str(&contents).map_err(|err| format!("{}: {err}", path.display()))
+    }
+}
+
+fn config_path() -> Option<PathBuf> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(config_dir.join("phasellus").join(CONFIG_FILENAME))
+}
@@ -0,0 +1,174 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::{BufReader, BufWriter};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::players::{Category, Scoreboard};
+
+const HIGHSCORE_FILENAME: &str = "highscores.json";
+const MAX_ENTRIES: usize = 10;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct HighScoreEntry {
+    /// `YYYY-MM-DD`, in UTC
+    pub date: String,
+    pub scoreboard: Scoreboard,
+}
+
+/// Best solo games, one table per rule set.
+#[derive(Default, Serialize, Deserialize)]
+pub struct HighScores {
+    tables: BTreeMap<String, Vec<HighScoreEntry>>,
+}
+
+impl HighScores {
+    /// Reads the table from the data directory. A missing file is an empty table.
+    pub fn load() -> Result<Self, String> {
+        let path = highscore_path().ok_or("Cannot find a directory to keep high scores")?;
+        let file = match fs::File::open(&path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err.to_string()),
+        };
+
+        serde_json::from_reader(BufReader::new(file)).map_err(|err| err.to_string())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = highscore_path().ok_or("Cannot find a directory to keep high scores")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+        }
+
+        let file = fs::File::create(&path).map_err(|err| err.to_string())?;
+        serde_json::to_writer(BufWriter::new(file), self).map_err(|err| err.to_string())
+    }
+
+    /// Records a finished game and returns its place in the table, if it made the cut.
+    pub fn record(&mut self, rule_set: &str, scoreboard: Scoreboard) -> Option<usize> {
+        let table = self.tables.entry(rule_set.to_string()).or_default();
+        let place = table
+            .iter()
+            .position(|entry| entry.scoreboard.total_score < scoreboard.total_score)
+            .unwrap_or(table.len());
+
+        if place >= MAX_ENTRIES {
+            return None;
+        }
+
+        table.insert(
+            place,
+            HighScoreEntry {
+                date: today(),
+                scoreboard,
+            },
+        );
+        table.truncate(MAX_ENTRIES);
+        Some(place)
+    }
+
+    pub fn tables(&self) -> impl Iterator<Item = (&'_ String, &'_ Vec<HighScoreEntry>)> {
+        self.tables.iter()
+    }
+}
+
+pub fn format_scorecard(scoreboard: &Scoreboard) -> String {
+    let mut scorecard = String::new();
+    for category in Category::ALL {
+        let value = category
+            .value(scoreboard)
+            .map_or_else(|| "-".to_string(), |value| value.to_string());
+        scorecard.push_str(&format!("{:<16}{value:>4}\n", category.name()));
+        if category == Category::Sixes {
+            scorecard.push_str(&format!("{:<16}{:>4}\n", "Bonus", scoreboard.bonus));
+        }
+    }
+    scorecard.push_str(&format!("{:<16}{:>4}", "Total", scoreboard.total_score));
+    scorecard
+}
+
+fn highscore_path() -> Option<PathBuf> {
+    let data_dir = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))?;
+
+    Some(data_dir.join("phasellus").join(HIGHSCORE_FILENAME))
+}
+
+fn today() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() / 86_400)
+        .unwrap_or(0) as i64;
+
+    // Days since 1970-01-01 to a civil date (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scoring(total_score: u16) -> Scoreboard {
+        Scoreboard {
+            total_score,
+            ..Scoreboard::default()
+        }
+    }
+
+    fn totals(high_scores: &HighScores, rule_set: &str) -> Vec<u16> {
+        high_scores.tables[rule_set]
+            .iter()
+            .map(|entry| entry.scoreboard.total_score)
+            .collect()
+    }
+
+    #[test]
+    fn games_are_ranked_best_first() {
+        let mut high_scores = HighScores::default();
+        assert_eq!(high_scores.record("Yacht", scoring(150)), Some(0));
+        assert_eq!(high_scores.record("Yacht", scoring(200)), Some(0));
+        assert_eq!(high_scores.record("Yacht", scoring(100)), Some(2));
+        // A tie goes below the game that got there first
+        assert_eq!(high_scores.record("Yacht", scoring(150)), Some(2));
+        assert_eq!(totals(&high_scores, "Yacht"), [200, 150, 150, 100]);
+    }
+
+    #[test]
+    fn a_full_table_keeps_the_best_games() {
+        let mut high_scores = HighScores::default();
+        for total in 1..=MAX_ENTRIES as u16 {
+            high_scores.record("Yacht", scoring(total * 10));
+        }
+        assert_eq!(high_scores.record("Yacht", scoring(10)), None);
+        assert_eq!(high_scores.record("Yacht", scoring(55)), Some(5));
+
+        let totals = totals(&high_scores, "Yacht");
+        assert_eq!(totals.len(), MAX_ENTRIES);
+        assert_eq!(totals.last(), Some(&20));
+    }
+
+    #[test]
+    fn rule_sets_keep_their_own_tables() {
+        let mut high_scores = HighScores::default();
+        high_scores.record("Yacht", scoring(100));
+        assert_eq!(high_scores.record("Yahtzee", scoring(50)), Some(0));
+        assert_eq!(totals(&high_scores, "Yacht"), [100]);
+        assert_eq!(totals(&high_scores, "Yahtzee"), [50]);
+    }
+}
@@ -0,0 +1,150 @@
+//! A minimal HTTP/1.1 server for the local JSON API. Each request is answered
+//! on the UI thread, so handlers see the same board as the terminal.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use cursive::{CbSink, Cursive};
+use serde::Serialize;
+
+const MAX_BODY_LEN: usize = 64 * 1024;
+const HANDLER_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long a connection may take to send its request before it is dropped
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: String,
+}
+
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    pub fn json<T: Serialize>(status: u16, value: &T) -> Self {
+        match serde_json::to_string(value) {
+            Ok(body) => Self { status, body },
+            Err(err) => Self::error(500, &err.to_string()),
+        }
+    }
+
+    pub fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            body: serde_json::json!({ "error": message }).to_string(),
+        }
+    }
+}
+
+/// Serves on the loopback interface only.
+pub fn serve(
+    port: u16,
+    cb_sink: CbSink,
+    handler: fn(&mut Cursive, Request) -> Response,
+) -> io::Result<()> {
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            if stream.set_read_timeout(Some(READ_TIMEOUT)).is_err() {
+                continue;
+            }
+            let cb_sink = cb_sink.clone();
+            thread::spawn(move || handle_connection(stream, cb_sink, handler));
+        }
+    });
+
+    Ok(())
+}
+
+fn handle_connection(
+    mut stream: TcpStream,
+    cb_sink: CbSink,
+    handler: fn(&mut Cursive, Request) -> Response,
+) {
+    let response = match read_request(&stream) {
+        Ok(request) => {
+            let (sender, receiver) = mpsc::channel();
+            let sent = cb_sink.send(Box::new(move |siv| {
+                let _ = sender.send(handler(siv, request));
+            }));
+            match sent {
+                Ok(()) => receiver
+                    .recv_timeout(HANDLER_TIMEOUT)
+                    .unwrap_or_else(|_| Response::error(503, "The board did not answer")),
+                Err(_) => Response::error(503, "The board is closing"),
+            }
+        }
+        Err(err) => Response::error(400, &err.to_string()),
+    };
+
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason_phrase(response.status),
+        response.body.len(),
+        response.body
+    );
+}
+
+fn read_request(stream: &TcpStream) -> io::Result<Request> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Err(invalid("malformed request line"));
+    };
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header)?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| invalid("invalid Content-Length"))?;
+            }
+        }
+    }
+    if content_length > MAX_BODY_LEN {
+        return Err(invalid("request body is too large"));
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    Ok(Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        body: String::from_utf8(body).map_err(|_| invalid("request body is not UTF-8"))?,
+    })
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "",
+    }
+}
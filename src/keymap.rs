@@ -0,0 +1,527 @@
+//! Which keys trigger which actions on the board. A binding is a sequence of
+//! keys separated by spaces, e.g. `ctrl-s` or `g g`.
+
+use std::collections::HashMap;
+
+use cursive::event::{Event, Key};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Action {
+    Quit,
+    Help,
+    AddPlayer,
+    AddBot,
+    DeletePlayer,
+    RenamePlayer,
+    AddTeam,
+    DeleteTeam,
+    NewMatch,
+    MatchStandings,
+    Tournament,
+    PauseClock,
+    Ones,
+    Twos,
+    Threes,
+    Fours,
+    Fives,
+    Sixes,
+    Choice,
+    FullHouse,
+    FourOfKind,
+    SmallStraight,
+    SmallStraightMiss,
+    LargeStraight,
+    LargeStraightMiss,
+    Yacht,
+    YachtMiss,
+    ClearScores,
+    Undo,
+    Save,
+    Load,
+    Export,
+    Hint,
+    Practice,
+    Roll,
+    HighScores,
+    ScrollLeft,
+    ScrollRight,
+    ScrollUp,
+    ScrollDown,
+    CommandPalette,
+}
+
+/// Where an action is listed in the help message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    Players,
+    Scores,
+    Files,
+    Practice,
+    Board,
+}
+
+impl Section {
+    pub const ALL: [Self; 5] = [
+        Self::Players,
+        Self::Scores,
+        Self::Files,
+        Self::Practice,
+        Self::Board,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Players => "Player Related Keybindings",
+            Self::Scores => "Score Related Keybindings",
+            Self::Files => "Save and Load datas",
+            Self::Practice => "Advice and Practice",
+            Self::Board => "Board",
+        }
+    }
+}
+
+impl Action {
+    pub const ALL: [Self; 41] = [
+        Self::Quit,
+        Self::Help,
+        Self::AddPlayer,
+        Self::AddBot,
+        Self::DeletePlayer,
+        Self::RenamePlayer,
+        Self::AddTeam,
+        Self::DeleteTeam,
+        Self::NewMatch,
+        Self::MatchStandings,
+        Self::Tournament,
+        Self::PauseClock,
+        Self::Ones,
+        Self::Twos,
+        Self::Threes,
+        Self::Fours,
+        Self::Fives,
+        Self::Sixes,
+        Self::Choice,
+        Self::FullHouse,
+        Self::FourOfKind,
+        Self::SmallStraight,
+        Self::SmallStraightMiss,
+        Self::LargeStraight,
+        Self::LargeStraightMiss,
+        Self::Yacht,
+        Self::YachtMiss,
+        Self::ClearScores,
+        Self::Undo,
+        Self::Save,
+        Self::Load,
+        Self::Export,
+        Self::Hint,
+        Self::Practice,
+        Self::Roll,
+        Self::HighScores,
+        Self::ScrollLeft,
+        Self::ScrollRight,
+        Self::ScrollUp,
+        Self::ScrollDown,
+        Self::CommandPalette,
+    ];
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Quit => "quit this program",
+            Self::Help => "show this help message",
+            Self::AddPlayer => "add player",
+            Self::AddBot => "add computer player",
+            Self::DeletePlayer => "delete player",
+            Self::RenamePlayer => "rename player",
+            Self::AddTeam => "form a team",
+            Self::DeleteTeam => "break up a team",
+            Self::NewMatch => "start a match of several games",
+            Self::MatchStandings => "show the match standings",
+            Self::Tournament => "show the tournament, or start one",
+            Self::PauseClock => "start or pause the clock",
+            Self::Ones => "add score at ones",
+            Self::Twos => "add score at twos",
+            Self::Threes => "add score at threes",
+            Self::Fours => "add score at fours",
+            Self::Fives => "add score at fives",
+            Self::Sixes => "add score at sixes",
+            Self::Choice => "add score at choice",
+            Self::FullHouse => "add score at full house",
+            Self::FourOfKind => "add score at four of a kind",
+            Self::SmallStraight => "add score at small straight",
+            Self::SmallStraightMiss => "scratch small straight",
+            Self::LargeStraight => "add score at large straight",
+            Self::LargeStraightMiss => "scratch large straight",
+            Self::Yacht => "add score at yacht",
+            Self::YachtMiss => "scratch yacht",
+            Self::ClearScores => "clear all scores",
+            Self::Undo => "undo the last score or rename",
+            Self::Save => "save the data into JSON files",
+            Self::Load => "load the data from JSON files",
+            Self::Export => "export the board as a CSV file",
+            Self::Hint => "get a hint for a dice hand",
+            Self::Practice => "start or leave a practice game",
+            Self::Roll => "roll the dice in a practice game",
+            Self::HighScores => "show the practice high scores",
+            Self::ScrollLeft => "scroll the board left",
+            Self::ScrollRight => "scroll the board right",
+            Self::ScrollUp => "scroll the board up",
+            Self::ScrollDown => "scroll the board down",
+            Self::CommandPalette => "search for an action to run",
+        }
+    }
+
+    pub fn section(self) -> Section {
+        match self {
+            Self::Quit
+            | Self::AddPlayer
+            | Self::AddBot
+            | Self::DeletePlayer
+            | Self::RenamePlayer
+            | Self::AddTeam
+            | Self::DeleteTeam => Section::Players,
+            Self::Save | Self::Load | Self::Export => Section::Files,
+            Self::Hint | Self::Practice | Self::Roll | Self::HighScores => Section::Practice,
+            Self::Help
+            | Self::CommandPalette
+            | Self::ScrollLeft
+            | Self::ScrollRight
+            | Self::ScrollUp
+            | Self::ScrollDown => Section::Board,
+            _ => Section::Scores,
+        }
+    }
+
+    /// Changes the game, so only the host of a network game may do it.
+    pub fn is_edit(self) -> bool {
+        matches!(
+            self,
+            Self::AddPlayer
+                | Self::AddBot
+                | Self::DeletePlayer
+                | Self::RenamePlayer
+                | Self::AddTeam
+                | Self::DeleteTeam
+                | Self::NewMatch
+                | Self::ClearScores
+                | Self::Practice
+                | Self::Load
+        )
+    }
+
+    /// Records a score typed in by hand.
+    pub fn is_score(self) -> bool {
+        matches!(
+            self,
+            Self::Ones
+                | Self::Twos
+                | Self::Threes
+                | Self::Fours
+                | Self::Fives
+                | Self::Sixes
+                | Self::Choice
+                | Self::FullHouse
+                | Self::FourOfKind
+                | Self::SmallStraight
+                | Self::SmallStraightMiss
+                | Self::LargeStraight
+                | Self::LargeStraightMiss
+                | Self::Yacht
+                | Self::YachtMiss
+        )
+    }
+
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Self::Quit => &["q"],
+            Self::Help => &["?"],
+            Self::AddPlayer => &["a"],
+            Self::AddBot => &["b"],
+            Self::DeletePlayer => &["d"],
+            Self::RenamePlayer => &["R"],
+            Self::AddTeam => &["t"],
+            Self::DeleteTeam => &["D"],
+            Self::NewMatch => &["M"],
+            Self::MatchStandings => &["m"],
+            Self::Tournament => &["B"],
+            Self::PauseClock => &["p"],
+            Self::Ones => &["1"],
+            Self::Twos => &["2"],
+            Self::Threes => &["3"],
+            Self::Fours => &["4"],
+            Self::Fives => &["5"],
+            Self::Sixes => &["6"],
+            Self::Choice => &["c"],
+            Self::FullHouse => &["h"],
+            Self::FourOfKind => &["k"],
+            Self::SmallStraight => &["s"],
+            Self::SmallStraightMiss => &["S"],
+            Self::LargeStraight => &["l"],
+            Self::LargeStraightMiss => &["L"],
+            Self::Yacht => &["y"],
+            Self::YachtMiss => &["Y"],
+            Self::ClearScores => &["C"],
+            Self::Undo => &["u"],
+            Self::Save => &["ctrl-s"],
+            Self::Load => &["ctrl-l"],
+            Self::Export => &["ctrl-e"],
+            Self::Hint => &["H"],
+            Self::Practice => &["P"],
+            Self::Roll => &["r"],
+            Self::HighScores => &["T"],
+            Self::ScrollLeft => &["left"],
+            Self::ScrollRight => &["right"],
+            Self::ScrollUp => &["up"],
+            Self::ScrollDown => &["down"],
+            Self::CommandPalette => &[":", "ctrl-p"],
+        }
+    }
+}
+
+/// One binding or several, as written in the config file
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Bindings {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Bindings {
+    fn as_slice(&self) -> &[String] {
+        match self {
+            Self::One(binding) => std::slice::from_ref(binding),
+            Self::Many(bindings) => bindings,
+        }
+    }
+}
+
+pub enum Lookup {
+    Action(Action),
+    /// The keys so far start a longer binding
+    Pending,
+    Unbound,
+}
+
+pub struct Keymap {
+    bindings: Vec<(Vec<Event>, Action)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new(&HashMap::new()).expect("the default keymap has no conflicts")
+    }
+}
+
+impl Keymap {
+    /// The default keys, with the actions in `overrides` bound to the given keys
+    /// instead. Fails on a key that cannot be read or on bindings that conflict.
+    pub fn new(overrides: &HashMap<Action, Bindings>) -> Result<Self, String> {
+        let mut bindings = Vec::new();
+        for action in Action::ALL {
+            let keys = match overrides.get(&action) {
+                Some(keys) => keys.as_slice().to_vec(),
+                None => action
+                    .default_keys()
+                    .iter()
+                    .map(|binding| binding.to_string())
+                    .collect(),
+            };
+            for binding in keys {
+                let sequence = parse_sequence(&binding)
+                    .ok_or_else(|| format!("cannot read the key `{binding}`"))?;
+                bindings.push((sequence, action));
+            }
+        }
+
+        let keymap = Self { bindings };
+        let conflicts = keymap.conflicts();
+        if conflicts.is_empty() {
+            Ok(keymap)
+        } else {
+            Err(format!("conflicting keys:\n{}", conflicts.join("\n")))
+        }
+    }
+
+    /// Pairs of bindings where one is the same as, or starts, the other.
+    pub fn conflicts(&self) -> Vec<String> {
+        let mut conflicts = Vec::new();
+        for (i, (lhs, lhs_action)) in self.bindings.iter().enumerate() {
+            for (rhs, rhs_action) in &self.bindings[i + 1..] {
+                if lhs.starts_with(rhs) || rhs.starts_with(lhs) {
+                    conflicts.push(format!(
+                        "  `{}` ({}) and `{}` ({})",
+                        sequence_name(lhs),
+                        lhs_action.description(),
+                        sequence_name(rhs),
+                        rhs_action.description()
+                    ));
+                }
+            }
+        }
+        conflicts
+    }
+
+    pub fn lookup(&self, keys: &[Event]) -> Lookup {
+        let mut is_prefix = false;
+        for (sequence, action) in &self.bindings {
+            if sequence == keys {
+                return Lookup::Action(*action);
+            }
+            is_prefix |= sequence.starts_with(keys);
+        }
+        if is_prefix {
+            Lookup::Pending
+        } else {
+            Lookup::Unbound
+        }
+    }
+
+    /// How the keys of `action` are shown to the user, e.g. `Ctrl-s`
+    pub fn keys_of(&self, action: Action) -> String {
+        self.bindings
+            .iter()
+            .filter(|(_, bound)| *bound == action)
+            .map(|(sequence, _)| sequence_name(sequence))
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+}
+
+fn parse_sequence(binding: &str) -> Option<Vec<Event>> {
+    let sequence = binding
+        .split_whitespace()
+        .map(parse_key)
+        .collect::<Option<Vec<_>>>()?;
+    (!sequence.is_empty()).then_some(sequence)
+}
+
+fn parse_key(key: &str) -> Option<Event> {
+    let single_char = |text: &str| {
+        let mut chars = text.chars();
+        match (chars.next(), chars.next()) {
+            (Some(ch), None) => Some(ch),
+            _ => None,
+        }
+    };
+
+    if let Some(ch) = single_char(key) {
+        return Some(Event::Char(ch));
+    }
+    let lower = key.to_ascii_lowercase();
+    if let Some(ch) = lower.strip_prefix("ctrl-").and_then(single_char) {
+        return Some(Event::CtrlChar(ch));
+    }
+    // Alt keeps the case of its key
+    if lower.starts_with("alt-") {
+        return single_char(&key[4..]).map(Event::AltChar);
+    }
+
+    let key = match lower.as_str() {
+        "space" => return Some(Event::Char(' ')),
+        "enter" => Key::Enter,
+        "esc" => Key::Esc,
+        "tab" => Key::Tab,
+        "backspace" => Key::Backspace,
+        "delete" => Key::Del,
+        "insert" => Key::Ins,
+        "left" => Key::Left,
+        "right" => Key::Right,
+        "up" => Key::Up,
+        "down" => Key::Down,
+        "home" => Key::Home,
+        "end" => Key::End,
+        "pageup" => Key::PageUp,
+        "pagedown" => Key::PageDown,
+        function => {
+            let number = function.strip_prefix('f')?.parse().ok()?;
+            if !(1..=12).contains(&number) {
+                return None;
+            }
+            Key::from_f(number)
+        }
+    };
+    Some(Event::Key(key))
+}
+
+fn sequence_name(sequence: &[Event]) -> String {
+    sequence.iter().map(key_name).collect::<Vec<_>>().join(" ")
+}
+
+fn key_name(event: &Event) -> String {
+    match event {
+        Event::Char(' ') => "Space".to_string(),
+        Event::Char(ch) => ch.to_string(),
+        Event::CtrlChar(ch) => format!("Ctrl-{ch}"),
+        Event::AltChar(ch) => format!("Alt-{ch}"),
+        Event::Key(Key::Del) => "Delete".to_string(),
+        Event::Key(Key::Ins) => "Insert".to_string(),
+        Event::Key(key) => format!("{key:?}"),
+        event => format!("{event:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keymap_with(overrides: &[(Action, &str)]) -> Result<Keymap, String> {
+        let overrides = overrides
+            .iter()
+            .map(|(action, binding)| (*action, Bindings::One(binding.to_string())))
+            .collect();
+        Keymap::new(&overrides)
+    }
+
+    #[test]
+    fn default_keys_do_not_conflict() {
+        assert!(Keymap::default().conflicts().is_empty());
+    }
+
+    #[test]
+    fn binding_that_starts_another_conflicts() {
+        let err = keymap_with(&[(Action::ClearScores, "g c"), (Action::Quit, "g")])
+            .err()
+            .expect("`g` starts `g c`");
+        assert!(err.contains("`g c`"));
+        assert!(err.contains("`g`"));
+    }
+
+    #[test]
+    fn chord_is_pending_until_complete() {
+        let keymap = keymap_with(&[(Action::ClearScores, "g c")]).unwrap();
+
+        assert!(matches!(
+            keymap.lookup(&[Event::Char('g')]),
+            Lookup::Pending
+        ));
+        assert!(matches!(
+            keymap.lookup(&[Event::Char('g'), Event::Char('c')]),
+            Lookup::Action(Action::ClearScores)
+        ));
+        assert!(matches!(
+            keymap.lookup(&[Event::Char('g'), Event::Char('x')]),
+            Lookup::Unbound
+        ));
+        assert!(matches!(
+            keymap.lookup(&[Event::Char('x')]),
+            Lookup::Unbound
+        ));
+    }
+
+    #[test]
+    fn override_replaces_default_key() {
+        let keymap = keymap_with(&[(Action::Ones, "o")]).unwrap();
+
+        assert!(matches!(
+            keymap.lookup(&[Event::Char('o')]),
+            Lookup::Action(Action::Ones)
+        ));
+        assert!(matches!(
+            keymap.lookup(&[Event::Char('1')]),
+            Lookup::Unbound
+        ));
+        assert_eq!(keymap.keys_of(Action::Ones), "o");
+    }
+}
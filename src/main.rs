@@ -1,26 +1,131 @@
 #![allow(clippy::unit_arg)]
 
+mod advisor;
+mod bot;
+mod cli;
+mod clock;
+mod config;
+mod highscore;
+#[cfg(feature = "http-api")]
+mod http;
+mod keymap;
+mod net;
+mod overlay;
 mod playboard;
 mod players;
+mod series;
+mod snapshot;
+mod theme;
+mod tournament;
+mod watch;
 
-use cursive::{
-    theme::{Palette, Theme},
-    traits::Nameable,
-    Cursive, CursiveExt,
-};
+use std::process;
+
+use cursive::{traits::Nameable, Cursive, CursiveExt};
 
 fn main() {
-    let palette = Palette::terminal_default();
+    let options = match cli::Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("phasellus: {err}\n\n{}", cli::USAGE);
+            process::exit(1);
+        }
+    };
+    if options.help {
+        println!("{}", cli::USAGE);
+        return;
+    }
+
+    let config = match config::Config::load() {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("phasellus: cannot read the config file: {err}");
+            process::exit(1);
+        }
+    };
+    let theme = options.theme.unwrap_or(config.theme);
+    let keymap = match keymap::Keymap::new(&config.keys) {
+        Ok(keymap) => keymap,
+        Err(err) => {
+            eprintln!("phasellus: cannot use the keys in the config file: {err}");
+            process::exit(1);
+        }
+    };
+
     let mut siv = Cursive::default();
-    siv.set_theme(Theme {
-        shadow: false,
-        palette,
-        ..Default::default()
-    });
+    siv.set_theme(theme.theme());
+
+    let mut board = playboard::PlayBoard::new();
+    board.set_styles(theme.board_styles());
+    board.set_keymap(keymap);
+    board.set_ascii(options.ascii.unwrap_or_else(playboard::prefers_ascii));
+    board.set_large_layout(options.large);
+    if options.practice {
+        board.start_practice();
+    }
+    if let Some(dir) = &options.overlay {
+        if let Err(err) = board.set_overlay(dir) {
+            eprintln!("phasellus: cannot write the overlay: {err}");
+            process::exit(1);
+        }
+    }
+
+    let cb_sink = siv.cb_sink().clone();
+    let started = if let Some(port) = options.host {
+        board
+            .host_game(port, options.name.as_deref(), cb_sink)
+            .map_err(|err| err.to_string())
+    } else if let (Some(addr), Some(name)) = (&options.join, &options.name) {
+        board
+            .join_game(addr, name, cb_sink)
+            .map_err(|err| err.to_string())
+    } else if let Some(path) = &options.watch {
+        board.spectate_file(path, cb_sink)
+    } else if let Some(addr) = &options.spectate {
+        board
+            .spectate_host(addr, cb_sink)
+            .map_err(|err| err.to_string())
+    } else if let Some(path) = &options.tournament {
+        board.open_tournament(path)
+    } else {
+        Ok(())
+    };
+    if let Err(err) = started {
+        eprintln!("phasellus: {err}");
+        process::exit(1);
+    }
+
+    let clock = options
+        .clock
+        .map(|(mode, seconds)| clock::ClockConfig {
+            mode,
+            seconds,
+            on_timeout: config
+                .clock
+                .map(|clock| clock.on_timeout)
+                .unwrap_or_default(),
+        })
+        .or(config.clock)
+        .map(|mut clock| {
+            clock.on_timeout = options.on_timeout.unwrap_or(clock.on_timeout);
+            clock
+        });
+    if let Some(clock) = clock {
+        board.start_clock(clock, siv.cb_sink().clone());
+    }
 
-    let board = playboard::PlayBoard::new();
+    #[cfg(feature = "http-api")]
+    if let Some(port) = options.http {
+        if let Err(err) = playboard::serve_api(port, siv.cb_sink().clone()) {
+            eprintln!("phasellus: cannot start the HTTP API: {err}");
+            process::exit(1);
+        }
+    }
 
     siv.add_layer(board.with_name("playboard"));
+    if options.tournament.is_some() {
+        playboard::show_tournament(&mut siv);
+    }
 
     siv.run();
 }
@@ -0,0 +1,225 @@
+//! Newline-delimited JSON over TCP. The host owns the game; clients only ask
+//! it to record scores and receive the whole `Players` state after every change.
+
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use cursive::{CbSink, Cursive};
+use serde::{Deserialize, Serialize};
+
+use crate::players::{Players, ScoreInput};
+
+pub type ClientId = u64;
+
+/// How long a client may keep the host waiting before it is dropped
+const WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Serialize, Deserialize)]
+pub enum ClientMessage {
+    Join {
+        name: String,
+    },
+    /// Only follows the game, without a seat at the table
+    Watch,
+    Score {
+        input: ScoreInput,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum ServerMessage {
+    Welcome { name: String },
+    State(Players),
+    Rejected(String),
+}
+
+struct Connection {
+    name: Option<String>,
+    stream: TcpStream,
+}
+
+#[derive(Clone)]
+pub struct Host {
+    clients: Arc<Mutex<HashMap<ClientId, Connection>>>,
+    /// Everyone who ever joined from another machine, so only they can rejoin
+    joined: Arc<Mutex<HashSet<String>>>,
+}
+
+impl Host {
+    /// Listens on every interface. Messages and disconnects are handed to the UI thread.
+    pub fn start(
+        port: u16,
+        cb_sink: CbSink,
+        on_message: fn(&mut Cursive, ClientId, ClientMessage),
+        on_disconnect: fn(&mut Cursive, ClientId),
+    ) -> io::Result<Self> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        let host = Self {
+            clients: Arc::new(Mutex::new(HashMap::new())),
+            joined: Arc::new(Mutex::new(HashSet::new())),
+        };
+
+        let clients = Arc::clone(&host.clients);
+        thread::spawn(move || {
+            let next_id = AtomicU64::new(0);
+            for stream in listener.incoming().flatten() {
+                let Ok(reader) = stream.try_clone() else {
+                    continue;
+                };
+                if stream.set_write_timeout(Some(WRITE_TIMEOUT)).is_err() {
+                    continue;
+                }
+                let id = next_id.fetch_add(1, Ordering::Relaxed);
+                clients
+                    .lock()
+                    .unwrap()
+                    .insert(id, Connection { name: None, stream });
+
+                let cb_sink = cb_sink.clone();
+                thread::spawn(move || {
+                    for message in read_messages::<ClientMessage>(reader) {
+                        let sent = cb_sink.send(Box::new(move |siv| on_message(siv, id, message)));
+                        if sent.is_err() {
+                            return;
+                        }
+                    }
+                    let _ = cb_sink.send(Box::new(move |siv| on_disconnect(siv, id)));
+                });
+            }
+        });
+
+        Ok(host)
+    }
+
+    /// A client that cannot keep up is dropped, so that it does not hold up the others.
+    pub fn send(&self, id: ClientId, message: &ServerMessage) {
+        let mut clients = self.clients.lock().unwrap();
+        let failed = clients
+            .get(&id)
+            .is_some_and(|connection| write_message(&connection.stream, message).is_err());
+        if failed {
+            drop_client(&mut clients, id);
+        }
+    }
+
+    pub fn broadcast(&self, message: &ServerMessage) {
+        let mut clients = self.clients.lock().unwrap();
+        let failed = clients
+            .iter()
+            .filter(|(_, connection)| write_message(&connection.stream, message).is_err())
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        for id in failed {
+            drop_client(&mut clients, id);
+        }
+    }
+
+    pub fn set_name(&self, id: ClientId, name: &str) {
+        if let Some(connection) = self.clients.lock().unwrap().get_mut(&id) {
+            connection.name = Some(name.to_string());
+            self.joined.lock().unwrap().insert(name.to_string());
+        }
+    }
+
+    /// Whether `name` once joined from another machine.
+    pub fn has_joined(&self, name: &str) -> bool {
+        self.joined.lock().unwrap().contains(name)
+    }
+
+    pub fn client_name(&self, id: ClientId) -> Option<String> {
+        self.clients.lock().unwrap().get(&id)?.name.clone()
+    }
+
+    pub fn is_connected(&self, name: &str) -> bool {
+        self.clients
+            .lock()
+            .unwrap()
+            .values()
+            .any(|connection| connection.name.as_deref() == Some(name))
+    }
+
+    pub fn disconnect(&self, id: ClientId) {
+        drop_client(&mut self.clients.lock().unwrap(), id);
+    }
+}
+
+fn drop_client(clients: &mut HashMap<ClientId, Connection>, id: ClientId) {
+    if let Some(connection) = clients.remove(&id) {
+        let _ = connection.stream.shutdown(std::net::Shutdown::Both);
+    }
+}
+
+pub struct Client {
+    /// `None` for spectators
+    name: Option<String>,
+    stream: TcpStream,
+}
+
+impl Client {
+    /// Connects to a host and asks to join as `name`, or just to watch without one.
+    pub fn connect(
+        addr: &str,
+        name: Option<&str>,
+        cb_sink: CbSink,
+        on_message: fn(&mut Cursive, ServerMessage),
+        on_disconnect: fn(&mut Cursive),
+    ) -> io::Result<Self> {
+        let stream = TcpStream::connect(addr)?;
+        let reader = stream.try_clone()?;
+        let client = Self {
+            name: name.map(str::to_string),
+            stream,
+        };
+        client.send(&match name {
+            Some(name) => ClientMessage::Join {
+                name: name.to_string(),
+            },
+            None => ClientMessage::Watch,
+        })?;
+
+        thread::spawn(move || {
+            for message in read_messages::<ServerMessage>(reader) {
+                if cb_sink
+                    .send(Box::new(move |siv| on_message(siv, message)))
+                    .is_err()
+                {
+                    return;
+                }
+            }
+            let _ = cb_sink.send(Box::new(on_disconnect));
+        });
+
+        Ok(client)
+    }
+
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn send(&self, message: &ClientMessage) -> io::Result<()> {
+        write_message(&self.stream, message)
+    }
+}
+
+fn write_message<T: Serialize>(mut stream: &TcpStream, message: &T) -> io::Result<()> {
+    let mut line = serde_json::to_vec(message).map_err(io::Error::other)?;
+    line.push(b'\n');
+    stream.write_all(&line)
+}
+
+/// Reads messages until the connection closes. Lines that do not parse are skipped.
+fn read_messages<T>(stream: TcpStream) -> impl Iterator<Item = T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    BufReader::new(stream)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+}
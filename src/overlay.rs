@@ -0,0 +1,89 @@
+//! Writes the board into a directory as `board.json` and `board.html`, for a
+//! browser source of streaming software to display.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::players::Players;
+use crate::snapshot::Snapshot;
+
+const JSON_FILENAME: &str = "board.json";
+const HTML_FILENAME: &str = "board.html";
+/// How often the page reloads itself, in seconds
+const REFRESH_SECONDS: u32 = 2;
+
+pub struct Overlay {
+    dir: PathBuf,
+}
+
+impl Overlay {
+    pub fn new(dir: &Path) -> Result<Self, String> {
+        fs::create_dir_all(dir).map_err(|err| format!("{}: {err}", dir.display()))?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+        })
+    }
+
+    pub fn write(&self, players: &Players) -> Result<(), String> {
+        let snapshot = Snapshot::of(players);
+        let json = serde_json::to_string_pretty(&snapshot).map_err(|err| err.to_string())?;
+        self.replace(JSON_FILENAME, &json)?;
+        self.replace(HTML_FILENAME, &render_html(&snapshot))
+    }
+
+    /// Writes through a temporary file, so a reader never sees half a file.
+    fn replace(&self, filename: &str, contents: &str) -> Result<(), String> {
+        let path = self.dir.join(filename);
+        let temp = self.dir.join(format!(".{filename}.tmp"));
+        fs::write(&temp, contents)
+            .and_then(|()| fs::rename(&temp, &path))
+            .map_err(|err| format!("{}: {err}", path.display()))
+    }
+}
+
+fn render_html(snapshot: &Snapshot) -> String {
+    let mut rows = String::new();
+    for player in &snapshot.players {
+        let is_current = snapshot.current_player == Some(player.name);
+        let _ = writeln!(
+            rows,
+            "<tr{}><td>{}</td><td>{}{}</td><td>{}</td></tr>",
+            if is_current { " class=\"current\"" } else { "" },
+            player.rank,
+            if is_current { "▶ " } else { "" },
+            escape_html(player.name),
+            player.total,
+        );
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta http-equiv="refresh" content="{REFRESH_SECONDS}">
+<title>phasellus</title>
+<style>
+body {{ background: transparent; color: white; font-family: sans-serif; font-size: 28px; }}
+table {{ border-collapse: collapse; }}
+td {{ padding: 4px 16px; text-shadow: 1px 1px 2px black; }}
+td:last-child {{ text-align: right; }}
+tr.current {{ color: gold; }}
+</style>
+</head>
+<body>
+<table>
+{rows}</table>
+</body>
+</html>
+"#
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,112 @@
+use std::io;
+
+use cursive::{CbSink, Cursive};
+use serde::Deserialize;
+
+use super::{finish_turn, schedule_bot_turn, PlayBoard};
+use crate::http::{self, Request, Response};
+use crate::players::Category;
+use crate::snapshot::Snapshot;
+
+#[derive(Deserialize)]
+struct ScoreRequest {
+    player: String,
+    category: Category,
+    score: u16,
+}
+
+/// Serves the local JSON API on `port`.
+pub fn serve_api(port: u16, cb_sink: CbSink) -> io::Result<()> {
+    http::serve(port, cb_sink, handle_request)
+}
+
+fn handle_request(siv: &mut Cursive, request: Request) -> Response {
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/players") => with_board(siv, |play_board| {
+            let players = play_board
+                .players
+                .iter()
+                .map(|(name, scoreboard)| {
+                    serde_json::json!({ "name": name, "total": scoreboard.total_score })
+                })
+                .collect::<Vec<_>>();
+            Response::json(200, &players)
+        }),
+        ("GET", "/board") => with_board(siv, |play_board| {
+            Response::json(200, &Snapshot::of(&play_board.players))
+        }),
+        ("POST", "/score") => post_score(siv, &request.body),
+        ("POST", "/undo") => post_undo(siv),
+        (_, "/players" | "/board" | "/score" | "/undo") => {
+            Response::error(405, "Method not allowed")
+        }
+        _ => Response::error(404, "No such endpoint"),
+    }
+}
+
+fn with_board(siv: &mut Cursive, f: impl FnOnce(&mut PlayBoard) -> Response) -> Response {
+    siv.call_on_name("playboard", f)
+        .unwrap_or_else(|| Response::error(503, "The board is not ready"))
+}
+
+fn post_score(siv: &mut Cursive, body: &str) -> Response {
+    let request: ScoreRequest = match serde_json::from_str(body) {
+        Ok(request) => request,
+        Err(err) => return Response::error(400, &err.to_string()),
+    };
+    if !request.category.is_possible(request.score) {
+        return Response::error(
+            400,
+            &format!(
+                "{} is not a possible score at {}",
+                request.score,
+                request.category.name()
+            ),
+        );
+    }
+
+    let mut was_over = None;
+    let response = with_board(siv, |play_board| {
+        if play_board.spectator.is_some() {
+            return Response::error(409, "A spectator board cannot be changed");
+        }
+        if play_board
+            .players
+            .get_player_score(&request.player)
+            .is_none()
+        {
+            return Response::error(
+                404,
+                &format!("There is no player named `{}`", request.player),
+            );
+        }
+
+        let input = request.category.input_for(request.score);
+        match play_board.record_score(&request.player, input) {
+            Ok(applied) => {
+                was_over = applied;
+                Response::json(200, &Snapshot::of(&play_board.players))
+            }
+            Err(err) => Response::error(409, &err),
+        }
+    });
+
+    if let Some(was_over) = was_over {
+        finish_turn(siv, was_over);
+    }
+    response
+}
+
+fn post_undo(siv: &mut Cursive) -> Response {
+    let response = with_board(siv, |play_board| {
+        if play_board.spectator.is_some() {
+            return Response::error(409, "A spectator board cannot be changed");
+        }
+        match play_board.undo() {
+            Ok(_) => Response::json(200, &Snapshot::of(&play_board.players)),
+            Err(err) => Response::error(409, &err),
+        }
+    });
+    schedule_bot_turn(siv);
+    response
+}
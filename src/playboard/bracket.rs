@@ -0,0 +1,364 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use cursive::{
+    event::Key,
+    traits::{Nameable, Scrollable},
+    views::{Dialog, EditView, LinearLayout, OnEventView, SelectView, TextView},
+    Cursive,
+};
+use unicode_width::UnicodeWidthStr;
+
+use super::{schedule_bot_turn, show_error, PlayBoard};
+use crate::tournament::{Tournament, TournamentFormat};
+
+const DEFAULT_PATH: &str = "tournament.json";
+const DEFAULT_GROUP_SIZE: &str = "4";
+
+/// The tournament this board plays, kept in `path` after every result.
+pub(super) struct ActiveTournament {
+    path: PathBuf,
+    tournament: Tournament,
+    /// The pairing on the board, as `(round, index)`
+    playing: Option<(usize, usize)>,
+}
+
+impl PlayBoard {
+    /// Picks up the tournament kept in `path`.
+    pub fn open_tournament(&mut self, path: &Path) -> Result<(), String> {
+        let tournament =
+            Tournament::load(path).map_err(|err| format!("{}: {err}", path.display()))?;
+        self.tournament = Some(ActiveTournament {
+            path: path.to_path_buf(),
+            tournament,
+            playing: None,
+        });
+        Ok(())
+    }
+
+    /// Whether the game on the board is a tournament game still to be recorded.
+    pub(super) fn is_tournament_game(&self) -> bool {
+        self.tournament
+            .as_ref()
+            .is_some_and(|active| active.playing.is_some())
+    }
+
+    /// Whether putting a pairing on the board would throw away a game that is
+    /// kept nowhere else.
+    fn would_lose_game(&self) -> bool {
+        !self.players.is_empty() && self.unsaved_changes
+    }
+
+    /// Puts a pairing on the board as a fresh game.
+    fn play_pairing(&mut self, round: usize, index: usize) -> Result<(), String> {
+        self.may_edit()?;
+        let active = self
+            .tournament
+            .as_mut()
+            .ok_or_else(|| "No tournament is being played".to_string())?;
+        let game = active.tournament.game_for(round, index)?;
+        active.playing = Some((round, index));
+
+        self.practice = None;
+        self.series = None;
+        self.players = game;
+        self.players_changed();
+        Ok(())
+    }
+
+    /// Feeds a finished tournament game back into the tournament and saves it.
+    fn record_tournament_game(&mut self) -> Option<Result<String, String>> {
+        let active = self.tournament.as_mut()?;
+        let (round, index) = active.playing.take()?;
+
+        // A game that cannot count, like a tie in a bracket, is played again
+        if let Err(err) = active.tournament.record(round, index, &self.players) {
+            return Some(self.play_pairing(round, index).and(Err(err)));
+        }
+        let result = active.tournament.save(&active.path).map(|()| {
+            let pairing = active.tournament.pairing(round, index);
+            let mut message = pairing
+                .map(|pairing| pairing.describe())
+                .unwrap_or_default();
+            if let Some(champion) = active.tournament.champion() {
+                message.push_str(&format!("\n\n{champion} wins the tournament!"));
+            } else if active.tournament.is_finished() {
+                message.push_str("\n\nEvery game of the tournament is played.");
+            }
+            message
+        });
+        // The game is kept in the tournament file from now on
+        if result.is_ok() {
+            self.unsaved_changes = false;
+        }
+        Some(result)
+    }
+}
+
+/// Records the game that just ended, if it was a tournament game.
+pub(super) fn record_result(siv: &mut Cursive) {
+    let Some(result) = siv
+        .call_on_name("playboard", PlayBoard::record_tournament_game)
+        .flatten()
+    else {
+        return;
+    };
+
+    match result {
+        Ok(message) => siv.add_layer(
+            Dialog::text(message)
+                .title("Tournament")
+                .button("Next game", |s| {
+                    s.pop_layer();
+                    show_tournament(s);
+                })
+                .button("Close", |s| {
+                    s.pop_layer();
+                }),
+        ),
+        Err(err) => {
+            show_error(siv, err);
+            schedule_bot_turn(siv);
+        }
+    }
+}
+
+pub fn show_tournament(siv: &mut Cursive) {
+    let Some((text, pending)) = siv
+        .call_on_name("playboard", |play_board: &mut PlayBoard| {
+            let tournament = &play_board.tournament.as_ref()?.tournament;
+            let pending = tournament
+                .pending()
+                .into_iter()
+                .filter_map(|(round, index)| {
+                    let pairing = tournament.pairing(round, index)?;
+                    Some((
+                        format!("Round {}: {}", round + 1, pairing.describe()),
+                        (round, index),
+                    ))
+                })
+                .collect::<Vec<_>>();
+            Some((tournament_text(tournament), pending))
+        })
+        .flatten()
+    else {
+        show_new_tournament(siv);
+        return;
+    };
+
+    let mut games = SelectView::new();
+    for (label, pairing) in pending {
+        games.add_item(label, pairing);
+    }
+    games.set_on_submit(|s, &(round, index): &(usize, usize)| {
+        s.pop_layer();
+        let would_lose_game = s
+            .call_on_name("playboard", |play_board: &mut PlayBoard| {
+                play_board.would_lose_game()
+            })
+            .unwrap_or(false);
+        if !would_lose_game {
+            play_pairing(s, round, index);
+            return;
+        }
+        s.add_layer(
+            Dialog::text("Play this tournament game?\nThe current board will be cleared.")
+                .title("Tournament")
+                .button("Ok", move |s| {
+                    s.pop_layer();
+                    play_pairing(s, round, index);
+                })
+                .button("Cancel", |s| {
+                    s.pop_layer();
+                }),
+        );
+    });
+
+    let mut content = LinearLayout::vertical().child(TextView::new(text).scrollable());
+    if !games.is_empty() {
+        content.add_child(TextView::new("\nChoose the next game to play"));
+        content.add_child(games.scrollable());
+    }
+
+    siv.add_layer(
+        OnEventView::new(
+            Dialog::new()
+                .title("Tournament")
+                .content(content)
+                .button("New", |s| {
+                    s.pop_layer();
+                    show_new_tournament(s);
+                })
+                .button("Close", |s| {
+                    s.pop_layer();
+                }),
+        )
+        .on_event(Key::Esc, |s| {
+            s.pop_layer();
+        }),
+    );
+}
+
+fn play_pairing(siv: &mut Cursive, round: usize, index: usize) {
+    let result = siv
+        .call_on_name("playboard", |play_board: &mut PlayBoard| {
+            play_board.play_pairing(round, index)
+        })
+        .expect("`playboard` must exists");
+    match result {
+        Ok(()) => schedule_bot_turn(siv),
+        Err(err) => show_error(siv, err),
+    }
+}
+
+/// The bracket round by round, or the table of every round-robin group.
+fn tournament_text(tournament: &Tournament) -> String {
+    let mut text = format!(
+        "{}, {} entrants\n",
+        tournament.format.describe(),
+        tournament.entrants.len()
+    );
+
+    match tournament.format {
+        TournamentFormat::SingleElimination => {
+            for (round, pairings) in tournament.rounds.iter().enumerate() {
+                text.push_str(&format!("\nRound {}\n", round + 1));
+                for pairing in pairings {
+                    text.push_str(&format!("  {}\n", pairing.describe()));
+                }
+            }
+            if let Some(champion) = tournament.champion() {
+                text.push_str(&format!("\nChampion: {champion}\n"));
+            }
+        }
+        TournamentFormat::RoundRobin { .. } => {
+            for (group, standings) in tournament.group_standings().iter().enumerate() {
+                let name_width = standings
+                    .iter()
+                    .map(|standing| standing.name.width_cjk())
+                    .max()
+                    .unwrap_or(0);
+                text.push_str(&format!("\nGroup {}\n", group + 1));
+                text.push_str(&format!("  {:name_width$}   W  D  L  Total\n", ""));
+                for standing in standings {
+                    let padding = name_width - standing.name.width_cjk();
+                    text.push_str(&format!(
+                        "  {}{:padding$}  {:>2} {:>2} {:>2}  {:>5}\n",
+                        standing.name,
+                        "",
+                        standing.wins,
+                        standing.draws,
+                        standing.losses,
+                        standing.total
+                    ));
+                }
+            }
+        }
+    }
+    text
+}
+
+fn show_new_tournament(siv: &mut Cursive) {
+    let mut formats = SelectView::new();
+    formats.add_item("Single elimination", false);
+    formats.add_item("Round robin groups", true);
+
+    siv.add_layer(
+        Dialog::new()
+            .title("New Tournament")
+            .content(
+                LinearLayout::vertical()
+                    .child(TextView::new("Give the entrants, separated by commas,"))
+                    .child(TextView::new("or a file with one name per line"))
+                    .child(EditView::new().with_name("tournament_entrants"))
+                    .child(TextView::new("Choose the format"))
+                    .child(formats.with_name("tournament_format"))
+                    .child(TextView::new("Entrants per group, for round robin"))
+                    .child(
+                        EditView::new()
+                            .content(DEFAULT_GROUP_SIZE)
+                            .with_name("tournament_group_size"),
+                    )
+                    .child(TextView::new("Keep the tournament in"))
+                    .child(
+                        EditView::new()
+                            .content(DEFAULT_PATH)
+                            .with_name("tournament_path"),
+                    ),
+            )
+            .button("Ok", start_tournament)
+            .button("Cancel", |s| {
+                s.pop_layer();
+            }),
+    );
+}
+
+fn start_tournament(siv: &mut Cursive) {
+    let get_content = |siv: &mut Cursive, name: &str| {
+        siv.call_on_name(name, |view: &mut EditView| view.get_content())
+            .map(|content| content.trim().to_string())
+            .unwrap_or_default()
+    };
+    let entrants = get_content(siv, "tournament_entrants");
+    let group_size = get_content(siv, "tournament_group_size");
+    let path = PathBuf::from(get_content(siv, "tournament_path"));
+    let is_round_robin = siv
+        .call_on_name("tournament_format", |view: &mut SelectView<bool>| {
+            view.selection()
+        })
+        .flatten()
+        .is_some_and(|is_round_robin| *is_round_robin);
+
+    let result = new_tournament(&entrants, is_round_robin, &group_size, path);
+    match result {
+        Ok(active) => {
+            siv.call_on_name("playboard", |play_board: &mut PlayBoard| {
+                play_board.tournament = Some(active);
+            });
+            siv.pop_layer();
+            show_tournament(siv);
+        }
+        Err(err) => show_error(siv, err),
+    }
+}
+
+fn new_tournament(
+    entrants: &str,
+    is_round_robin: bool,
+    group_size: &str,
+    path: PathBuf,
+) -> Result<ActiveTournament, String> {
+    let format = if is_round_robin {
+        let group_size = group_size
+            .parse()
+            .map_err(|_| format!("`{group_size}` is not a group size"))?;
+        TournamentFormat::RoundRobin { group_size }
+    } else {
+        TournamentFormat::SingleElimination
+    };
+    let tournament = Tournament::new(format, read_entrants(entrants)?)?;
+    tournament.save(&path)?;
+    Ok(ActiveTournament {
+        path,
+        tournament,
+        playing: None,
+    })
+}
+
+/// Entrants typed in, or read from the file they name.
+fn read_entrants(entrants: &str) -> Result<Vec<String>, String> {
+    let path = Path::new(entrants);
+    let names = if !entrants.contains(',') && path.is_file() {
+        let contents = fs::read_to_string(path).map_err(|err| err.to_string())?;
+        contents.lines().map(str::to_string).collect::<Vec<_>>()
+    } else {
+        entrants.split(',').map(str::to_string).collect()
+    };
+
+    Ok(names
+        .iter()
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect())
+}
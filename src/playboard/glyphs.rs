@@ -0,0 +1,69 @@
+use std::env;
+
+/// Characters the board is drawn with.
+pub(super) struct Glyphs {
+    pub(super) horizontal: &'static str,
+    pub(super) vertical: &'static str,
+    /// Joints of a vertical line at the top, at crossings and at the bottom
+    pub(super) left_edge: [&'static str; 3],
+    pub(super) inner_edge: [&'static str; 3],
+    pub(super) right_edge: [&'static str; 3],
+    /// An open category
+    pub(super) empty: &'static str,
+    pub(super) secured: &'static str,
+    pub(super) out_of_reach: &'static str,
+    pub(super) scroll_x: &'static str,
+    pub(super) scroll_y: &'static str,
+    /// Between the rolls of a bot turn in the log
+    pub(super) arrow: &'static str,
+}
+
+pub(super) const UNICODE: Glyphs = Glyphs {
+    horizontal: "─",
+    vertical: "│",
+    left_edge: ["┌", "├", "└"],
+    inner_edge: ["┬", "┼", "┴"],
+    right_edge: ["┐", "┤", "┘"],
+    empty: "·",
+    secured: "✓",
+    out_of_reach: "✗",
+    scroll_x: "←→",
+    scroll_y: "↑↓",
+    arrow: " → ",
+};
+
+pub(super) const ASCII: Glyphs = Glyphs {
+    horizontal: "-",
+    vertical: "|",
+    left_edge: ["+", "+", "+"],
+    inner_edge: ["+", "+", "+"],
+    right_edge: ["+", "+", "+"],
+    empty: ".",
+    secured: "+",
+    out_of_reach: "x",
+    scroll_x: "<>",
+    scroll_y: "^v",
+    arrow: " -> ",
+};
+
+/// Whether the terminal is unlikely to show box-drawing characters: a locale
+/// that is not UTF-8, or a terminal type known for plain ASCII. Windows keeps
+/// no locale in the environment, so an old code page there needs `--ascii`.
+pub fn prefers_ascii() -> bool {
+    let is_plain_terminal = env::var("TERM")
+        .is_ok_and(|term| matches!(term.as_str(), "dumb" | "vt52" | "vt100" | "vt102" | "vt220"));
+
+    // The first of these that is set decides, as in the C library
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .into_iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.is_empty());
+    // No locale at all is the C locale, as on a serial console
+    let is_plain_locale = !cfg!(windows)
+        && locale.is_none_or(|locale| {
+            let locale = locale.to_ascii_lowercase();
+            !locale.contains("utf-8") && !locale.contains("utf8")
+        });
+
+    is_plain_terminal || is_plain_locale
+}
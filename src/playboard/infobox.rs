@@ -1,56 +1,156 @@
 use cursive::{
-    event::{Event, EventResult},
-    view::View,
-    views::Dialog,
-    Printer, Vec2,
+    event::Key,
+    traits::{Nameable, Scrollable},
+    views::{Dialog, EditView, LinearLayout, OnEventView, TextView},
+    Cursive,
 };
+use unicode_width::UnicodeWidthStr;
 
-const INFOBOX_INNER_SIZE: (usize, usize) = (82, 18);
+use crate::keymap::{Action, Keymap, Section};
 
-pub(super) struct InfoBox;
+const COLUMN_GAP: usize = 4;
 
-impl View for InfoBox {
-    fn draw(&self, printer: &Printer) {
-        let printer = printer.inner_size(INFOBOX_INNER_SIZE);
-        Dialog::new().title("Help Message").draw(&printer);
+/// Sections in the left column; the rest go to the right
+const LEFT_SECTIONS: [Section; 4] = [
+    Section::Players,
+    Section::Files,
+    Section::Practice,
+    Section::Board,
+];
 
-        printer.print((2, 2), "Keybindings for phasellus program");
+/// Keys of the practice dice tray, which are not in the keymap
+const PRACTICE_KEYS: [(&str, &str); 2] = [
+    ("1 ~ 5", "hold a die while practicing"),
+    ("Enter", "score the dice while practicing"),
+];
 
-        printer.print((2, 4), "<Player Related Keybindings>");
-        printer.print((2, 5), "a: add player");
-        printer.print((2, 6), "d: delete player");
-        printer.print((2, 7), "q: quit this program");
+/// A line of the help message
+#[derive(Clone)]
+pub(super) struct HelpEntry {
+    section: Section,
+    keys: String,
+    description: &'static str,
+}
 
-        printer.print((2, 9), "<Save and Load datas>");
-        printer.print((2, 10), "Ctrl-s: save the data into JSON files");
-        printer.print((2, 11), "Ctrl-l: load the data from JSON files");
+impl HelpEntry {
+    fn matches(&self, query: &str) -> bool {
+        self.keys.to_lowercase().contains(query) || self.description.to_lowercase().contains(query)
+    }
+}
 
-        printer.print((43, 4), "<Score Related Keybindings>");
-        printer.print((43, 5), "1 ~ 6: add score at ones, ..., sixes");
-        printer.print((43, 6), "c: add score at choice");
-        printer.print((43, 6), "h: add score at full house");
-        printer.print((43, 7), "k: add score at four of a kind");
-        printer.print((43, 8), "s: add score at small straight");
-        printer.print((43, 9), "l: add score at large straight");
-        printer.print((43, 10), "y: add score at yacht");
-        printer.print((43, 11), "s: add score at small straight");
-        printer.print((43, 12), "l: add score at large straight");
-        printer.print((43, 13), "y: add score at yacht");
-        printer.print((43, 14), "C: clear all scores");
+/// Every action with the keys `keymap` binds it to.
+pub(super) fn help_entries(keymap: &Keymap) -> Vec<HelpEntry> {
+    let mut entries = Action::ALL
+        .into_iter()
+        .map(|action| HelpEntry {
+            section: action.section(),
+            keys: keymap.keys_of(action),
+            description: action.description(),
+        })
+        .collect::<Vec<_>>();
+    entries.extend(PRACTICE_KEYS.map(|(keys, description)| HelpEntry {
+        section: Section::Practice,
+        keys: keys.to_string(),
+        description,
+    }));
+    entries
+}
 
-        printer.print((2, 16), "Press `q` to close this help message");
+/// The help message in two columns, keeping the entries that match `query`.
+fn help_text(entries: &[HelpEntry], query: &str) -> String {
+    let query = query.trim().to_lowercase();
+    let entries = entries
+        .iter()
+        .filter(|entry| entry.matches(&query))
+        .collect::<Vec<_>>();
+    if entries.is_empty() {
+        return format!("No keys match `{query}`");
     }
 
-    fn required_size(&mut self, _: Vec2) -> Vec2 {
-        INFOBOX_INNER_SIZE.into()
+    let (left, right): (Vec<_>, Vec<_>) = Section::ALL
+        .into_iter()
+        .partition(|section| LEFT_SECTIONS.contains(section));
+    let left = column_lines(&entries, &left);
+    let right = column_lines(&entries, &right);
+
+    let left_width = left
+        .iter()
+        .map(|line| line.width())
+        .max()
+        .map_or(0, |width| width + COLUMN_GAP);
+    let mut text = String::new();
+    for i in 0..left.len().max(right.len()) {
+        let left = left.get(i).map(String::as_str).unwrap_or_default();
+        let right = right.get(i).map(String::as_str).unwrap_or_default();
+        let padding = " ".repeat(left_width - left.width());
+        text.push_str(format!("{left}{padding}{right}").trim_end());
+        text.push('\n');
     }
+    text
+}
 
-    fn on_event(&mut self, event: Event) -> EventResult {
-        match event {
-            Event::Char('q') => EventResult::with_cb(|siv| {
-                siv.pop_layer();
-            }),
-            _ => EventResult::Ignored,
+fn column_lines(entries: &[&HelpEntry], sections: &[Section]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for section in sections {
+        let mut section_entries = entries
+            .iter()
+            .filter(|entry| entry.section == *section)
+            .peekable();
+        if section_entries.peek().is_none() {
+            continue;
         }
+
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push(format!("<{}>", section.title()));
+        lines.extend(section_entries.map(|entry| format!("{}: {}", entry.keys, entry.description)));
     }
+    lines
+}
+
+pub(super) fn show_help(siv: &mut Cursive, entries: Vec<HelpEntry>) {
+    let text = help_text(&entries, "");
+
+    siv.add_layer(
+        OnEventView::new(
+            Dialog::around(
+                LinearLayout::vertical()
+                    .child(TextView::new(
+                        "Keybindings for phasellus program. Type to search.",
+                    ))
+                    .child(
+                        EditView::new()
+                            .on_edit(move |s, query, _| {
+                                let text = help_text(&entries, query);
+                                s.call_on_name("help_text", |view: &mut TextView| {
+                                    view.set_content(text);
+                                });
+                            })
+                            .with_name("help_search"),
+                    )
+                    .child(TextView::new(text).with_name("help_text").scrollable())
+                    .child(TextView::new("Press `q` or Esc to close this help message")),
+            )
+            .title("Help Message"),
+        )
+        // `q` goes to the search once there is something typed
+        .on_pre_event('q', |s| {
+            let is_searching = s
+                .call_on_name("help_search", |view: &mut EditView| {
+                    !view.get_content().is_empty()
+                })
+                .unwrap_or(false);
+            if !is_searching {
+                s.pop_layer();
+            } else if let Some(callback) =
+                s.call_on_name("help_search", |view: &mut EditView| view.insert('q'))
+            {
+                callback(s);
+            }
+        })
+        .on_event(Key::Esc, |s| {
+            s.pop_layer();
+        }),
+    );
 }
@@ -0,0 +1,122 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use cursive::{views::Dialog, CbSink, Cursive};
+
+use super::{read_changed_file, schedule_bot_turn, PlayBoard};
+use crate::players::Players;
+use crate::watch::FileWatcher;
+
+pub(super) struct WatchedFile {
+    path: PathBuf,
+    _watcher: FileWatcher,
+}
+
+impl PlayBoard {
+    /// Reloads the game whenever `path` changes on disk.
+    pub(super) fn watch_file(&mut self, path: &Path, cb_sink: CbSink) {
+        self.watched = Some(WatchedFile {
+            path: path.to_path_buf(),
+            _watcher: FileWatcher::start(path.to_path_buf(), cb_sink, reload_watched_file),
+        });
+    }
+
+    /// Saving into the watched file leaves nothing unsaved.
+    pub(super) fn mark_saved(&mut self, path: &Path) {
+        let is_watched = self
+            .watched
+            .as_ref()
+            .is_some_and(|watched| is_same_file(&watched.path, path));
+        if self.watched.is_none() || is_watched {
+            self.unsaved_changes = false;
+        }
+    }
+}
+
+fn is_same_file(lhs: &Path, rhs: &Path) -> bool {
+    match (fs::canonicalize(lhs), fs::canonicalize(rhs)) {
+        (Ok(lhs), Ok(rhs)) => lhs == rhs,
+        _ => lhs == rhs,
+    }
+}
+
+fn reload_watched_file(siv: &mut Cursive) {
+    let conflict = siv
+        .call_on_name("playboard", |play_board: &mut PlayBoard| {
+            if play_board.conflict_pending {
+                return None;
+            }
+            let path = play_board.watched.as_ref()?.path.clone();
+            let theirs = read_changed_file(&path)?;
+
+            if play_board.unsaved_changes {
+                play_board.conflict_pending = true;
+                return Some((path, theirs));
+            }
+
+            play_board.set_players(theirs);
+            play_board.broadcast_players();
+            None
+        })
+        .flatten();
+
+    match conflict {
+        Some((path, theirs)) => show_conflict(siv, &path, theirs),
+        None => schedule_bot_turn(siv),
+    }
+}
+
+fn show_conflict(siv: &mut Cursive, path: &Path, theirs: Players) {
+    let use_file = theirs.clone();
+    let merge = theirs;
+
+    siv.add_layer(
+        Dialog::text(format!(
+            "{} changed on disk,\nbut this board has changes that are not saved.",
+            path.display()
+        ))
+        .title("File Changed")
+        .button("Use the file", move |s| {
+            let theirs = use_file.clone();
+            s.call_on_name("playboard", |play_board: &mut PlayBoard| {
+                play_board.set_players(theirs);
+                play_board.broadcast_players();
+                play_board.unsaved_changes = false;
+                play_board.conflict_pending = false;
+            });
+            s.pop_layer();
+            schedule_bot_turn(s);
+        })
+        .button("Keep mine", |s| {
+            s.call_on_name("playboard", |play_board: &mut PlayBoard| {
+                play_board.conflict_pending = false;
+            });
+            s.pop_layer();
+        })
+        .button("Merge", move |s| {
+            let conflicts = s
+                .call_on_name("playboard", |play_board: &mut PlayBoard| {
+                    let conflicts = play_board.players.merge(&merge);
+                    play_board.players_changed();
+                    play_board.conflict_pending = false;
+                    conflicts
+                })
+                .unwrap_or_default();
+            s.pop_layer();
+
+            if !conflicts.is_empty() {
+                s.add_layer(
+                    Dialog::text(format!(
+                        "Both sides scored these differently:\n\n{}",
+                        conflicts.join("\n")
+                    ))
+                    .title("Merge Conflicts")
+                    .button("Ok", |s| {
+                        s.pop_layer();
+                    }),
+                );
+            }
+            schedule_bot_turn(s);
+        }),
+    );
+}
@@ -36,3 +36,10 @@ macro_rules! score_event {
         ))
     };
 }
+
+#[macro_export]
+macro_rules! player_event {
+    ($title: literal, $callback: expr) => {
+        EventResult::with_cb_once(move |siv| player_picker::choose_player(siv, $title, $callback))
+    };
+}
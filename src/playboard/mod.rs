@@ -1,324 +1,573 @@
 #[macro_use]
 mod macros;
+#[cfg(feature = "http-api")]
+mod api;
+mod bracket;
+mod glyphs;
 mod infobox;
+mod live_reload;
+mod network;
+mod palette;
+mod player_picker;
+mod practice;
+mod rows;
+mod spectator;
+mod standings;
+mod timer;
 
 use std::cell::Cell;
 use std::cmp;
 use std::fs;
 use std::io::{BufReader, BufWriter};
+use std::ops::Range;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
 
 use cursive::{
     event::{Event, EventResult, Key},
+    theme::Style,
     traits::Nameable,
     view::View,
-    views::{Button, Dialog, EditView, LinearLayout, OnEventView, TextView},
+    views::{Button, Checkbox, Dialog, EditView, LinearLayout, OnEventView, SelectView, TextView},
     Cursive, Printer, Vec2,
 };
 use unicode_width::UnicodeWidthStr;
 
-use crate::players::{Players, ScoreInput};
-
-// Draw Scoreboard lines
-const HORIZONTAL_LINE: &str = "─";
-const VERTICAL_LINE: &str = "│";
-const TOP_LEFT_CORNER: &str = "┌";
-const TOP_RIGHT_CORNER: &str = "┐";
-const BOTTOM_LEFT_CORNER: &str = "└";
-const BOTTOM_RIGHT_CORNER: &str = "┘";
-const HORIZ_DOWN: &str = "┬";
-const HORIZ_UP: &str = "┴";
-const VERT_LEFT: &str = "┤";
-const VERT_RIGHT: &str = "├";
-const HORIZ_VERT: &str = "┼";
+use crate::advisor::{Advisor, Hint, MAX_REROLLS};
+use crate::bot::{self, Difficulty, Rng, Turn};
+use crate::clock::{self, Clock};
+use crate::keymap::{Action, Keymap, Lookup};
+use crate::overlay::Overlay;
+use crate::players::{Dice, Players, ScoreInput, TeamMode};
+use crate::series::{self, Series};
+use crate::snapshot;
+use crate::theme::{BoardStyles, ThemeName};
+use bracket::ActiveTournament;
+use glyphs::Glyphs;
+use live_reload::WatchedFile;
+use network::Network;
+use practice::{DiceTray, PRACTICE_PLAYER};
+use rows::{board_rows, Row, Shade};
+use spectator::{Spectator, LARGE_COLUMN_PADDING};
+use timer::Timer;
+
+#[cfg(feature = "http-api")]
+pub use api::serve_api;
+pub use bracket::show_tournament;
+pub use glyphs::prefers_ascii;
 
 const MIN_PLAYER_NAME_OFFSET: usize = 6;
 
+// Board geometry
+/// Top border, player names and the line under them
+const HEADER_HEIGHT: usize = 3;
+/// The time left, under the player names
+const CLOCK_LINES: usize = 1;
+const PRACTICE_LINES: usize = 2;
+const LABEL_WIDTH: usize = 25;
+const COMPACT_LABEL_WIDTH: usize = 14;
+
+// Bot turns
+const BOT_DELAY: Duration = Duration::from_millis(700);
+const LOG_LINES: usize = 3;
+
 pub struct PlayBoard {
     players: Players,
+    rows: Vec<Row>,
+    styles: BoardStyles,
+    glyphs: &'static Glyphs,
+    keymap: Keymap,
+    /// Keys typed so far of a binding with several keys
+    pending_keys: Vec<Event>,
     x_offset: Cell<usize>,
     y_offset: Cell<usize>,
     username_offset: Cell<usize>,
-    width: Cell<usize>,
+    /// First player column on screen
+    scroll_x: Cell<usize>,
+    /// Board rows scrolled off the top
+    scroll_y: Cell<usize>,
+    rng: Rng,
+    bot_pending: bool,
+    log: Vec<String>,
+    practice: Option<DiceTray>,
+    network: Option<Network>,
+    spectator: Option<Spectator>,
+    large: bool,
+    watched: Option<WatchedFile>,
+    /// Local changes not written to the watched file yet
+    unsaved_changes: bool,
+    conflict_pending: bool,
+    overlay: Option<Overlay>,
+    /// The match this game is part of, with the games already played
+    series: Option<Series>,
+    tournament: Option<ActiveTournament>,
+    timer: Option<Timer>,
 }
 
 impl PlayBoard {
     pub fn new() -> Self {
         Self {
             players: Players::new(),
+            rows: board_rows(false),
+            styles: ThemeName::default().board_styles(),
+            glyphs: &glyphs::UNICODE,
+            keymap: Keymap::default(),
+            pending_keys: Vec::new(),
             x_offset: Cell::new(1),
             y_offset: Cell::new(1),
-            username_offset: Cell::new(25),
-            width: Cell::new(0),
+            username_offset: Cell::new(LABEL_WIDTH),
+            scroll_x: Cell::new(0),
+            scroll_y: Cell::new(0),
+            rng: Rng::from_time(),
+            bot_pending: false,
+            log: Vec::new(),
+            practice: None,
+            network: None,
+            spectator: None,
+            large: false,
+            watched: None,
+            unsaved_changes: false,
+            conflict_pending: false,
+            overlay: None,
+            series: None,
+            tournament: None,
+            timer: None,
         }
     }
 
-    fn draw_init(&self, printer: &Printer) {
-        let (term_x, term_y) = printer.size.pair();
+    /// Called after every change made on this board.
+    fn players_changed(&mut self) {
+        self.unsaved_changes = true;
+        self.refresh_rows();
+        self.follow_clock();
+        self.broadcast_players();
+        self.write_overlay();
+    }
+
+    #[inline]
+    pub fn set_styles(&mut self, styles: BoardStyles) {
+        self.styles = styles;
+    }
+
+    #[inline]
+    pub fn set_keymap(&mut self, keymap: Keymap) {
+        self.keymap = keymap;
+    }
 
-        if self.players.is_empty() {
-            self.width.set(25);
+    /// Plain ASCII borders and marks, for terminals without box drawing.
+    #[inline]
+    pub fn set_ascii(&mut self, ascii: bool) {
+        self.glyphs = if ascii {
+            &glyphs::ASCII
+        } else {
+            &glyphs::UNICODE
+        };
+    }
+
+    fn style_of(&self, shade: Shade) -> Style {
+        match shade {
+            Shade::Plain => Style::none(),
+            Shade::Empty => self.styles.empty,
+            Shade::Scratched => self.styles.scratched,
+            Shade::Bonus => self.styles.bonus,
+            Shade::OutOfReach => self.styles.out_of_reach,
         }
-        self.x_offset
-            .set(term_x.saturating_sub(self.width.get()).saturating_div(2));
-        self.y_offset
-            .set(term_y.saturating_div(2).saturating_sub(11));
+    }
 
-        let x_offset = self.x_offset.get();
-        let y_offset = self.y_offset.get();
-        let username_offset = self.username_offset.get();
+    /// Keeps a copy of the board in `dir` for stream overlays.
+    pub fn set_overlay(&mut self, dir: &Path) -> Result<(), String> {
+        let overlay = Overlay::new(dir)?;
+        overlay.write(&self.players)?;
+        self.overlay = Some(overlay);
+        Ok(())
+    }
 
-        printer.print(
-            (1, term_y.saturating_sub(1)),
-            "Press `?` to show the help message.",
-        );
+    fn write_overlay(&mut self) {
+        let Some(overlay) = &self.overlay else {
+            return;
+        };
+        if let Err(err) = overlay.write(&self.players) {
+            self.push_log(format!("Cannot write the overlay: {err}"));
+        }
+    }
 
-        for i in 1..username_offset {
-            printer.print((x_offset + i, y_offset), HORIZONTAL_LINE);
-            printer.print((x_offset + i, y_offset + 2), HORIZONTAL_LINE);
-            printer.print((x_offset + i, y_offset + 9), HORIZONTAL_LINE);
-            printer.print((x_offset + i, y_offset + 12), HORIZONTAL_LINE);
-            printer.print((x_offset + i, y_offset + 19), HORIZONTAL_LINE);
-            printer.print((x_offset + i, y_offset + 20), HORIZONTAL_LINE);
-            printer.print((x_offset + i, y_offset + 22), HORIZONTAL_LINE);
-        }
-        for i in y_offset..(y_offset + 22) {
-            printer.print((x_offset, i), VERTICAL_LINE);
-            printer.print(
-                (x_offset + username_offset.saturating_sub(1), i),
-                VERTICAL_LINE,
-            );
-            printer.print((x_offset + username_offset, i), VERTICAL_LINE);
+    /// Takes back the last score. Returns whose score it was.
+    fn undo(&mut self) -> Result<String, String> {
+        self.may_edit()?;
+        self.may_change_card()?;
+        let name = self.players.undo()?;
+        self.players_changed();
+        Ok(name)
+    }
+
+    #[inline]
+    fn column_width(&self, name: &str) -> usize {
+        if self.large {
+            self.content_width(name) + LARGE_COLUMN_PADDING
+        } else {
+            self.content_width(name)
         }
-        printer.print((x_offset, y_offset), TOP_LEFT_CORNER);
-        printer.print((x_offset + username_offset, y_offset), TOP_RIGHT_CORNER);
-        printer.print(
-            (x_offset + username_offset, y_offset + 22),
-            BOTTOM_RIGHT_CORNER,
-        );
-        printer.print((x_offset, y_offset + 22), BOTTOM_LEFT_CORNER);
+    }
 
-        printer.print(
-            (x_offset + username_offset.saturating_sub(1), y_offset),
-            HORIZ_DOWN,
-        );
-        printer.print(
-            (x_offset + username_offset.saturating_sub(1), y_offset + 2),
-            HORIZ_VERT,
-        );
-        printer.print(
-            (x_offset + username_offset.saturating_sub(1), y_offset + 9),
-            HORIZ_VERT,
-        );
-        printer.print(
-            (x_offset + username_offset.saturating_sub(1), y_offset + 12),
-            HORIZ_VERT,
-        );
-        printer.print(
-            (x_offset + username_offset.saturating_sub(1), y_offset + 19),
-            HORIZ_VERT,
-        );
-        printer.print(
-            (x_offset + username_offset.saturating_sub(1), y_offset + 20),
-            HORIZ_VERT,
-        );
-        printer.print(
-            (x_offset + username_offset.saturating_sub(1), y_offset + 22),
-            HORIZ_UP,
+    /// Wide enough for the name, the time left and, in a team game, what the team row shows.
+    #[inline]
+    fn content_width(&self, name: &str) -> usize {
+        let clock_width = self.game_clock().map_or(0, Clock::max_width);
+        cmp::max(
+            str_terminal_len(name),
+            str_terminal_len(&rows::team_label(&self.players, name)),
+        )
+        .max(clock_width + 3)
+    }
+
+    /// The board header, with a line for the clock when there is one.
+    #[inline]
+    fn header_height(&self) -> usize {
+        if self.game_clock().is_some() {
+            HEADER_HEIGHT + CLOCK_LINES
+        } else {
+            HEADER_HEIGHT
+        }
+    }
+
+    fn set_players(&mut self, players: Players) {
+        self.players = players;
+        self.refresh_rows();
+        self.follow_clock();
+        self.write_overlay();
+    }
+
+    /// The team rows come and go with the teams.
+    #[inline]
+    fn refresh_rows(&mut self) {
+        self.rows = board_rows(!self.players.teams().is_empty());
+    }
+
+    /// Replaces the board with a single player game played with the built-in dice.
+    pub fn start_practice(&mut self) {
+        self.players = Players::new();
+        self.players
+            .add_player(PRACTICE_PLAYER)
+            .expect("an empty board takes any valid name");
+        self.refresh_rows();
+        self.log.clear();
+        self.practice = Some(DiceTray::default());
+        self.series = None;
+        // A file changing on disk would replace the practice card
+        self.watched = None;
+        self.reset_clock();
+        self.write_overlay();
+    }
+
+    fn push_log(&mut self, line: String) {
+        self.log.push(line);
+        if self.log.len() > LOG_LINES {
+            self.log.remove(0);
+        }
+    }
+
+    /// Chooses the label column, the player columns that fit on screen and where
+    /// the board goes. Returns the range of players shown.
+    fn layout(&self, size: Vec2) -> Range<usize> {
+        let (term_x, term_y) = size.pair();
+        let widths = self
+            .players
+            .iter()
+            .map(|(name, _)| self.column_width(name))
+            .collect::<Vec<_>>();
+        let total_width = widths.iter().sum::<usize>();
+
+        let label_width = if LABEL_WIDTH + total_width < term_x {
+            LABEL_WIDTH
+        } else {
+            COMPACT_LABEL_WIDTH
+        };
+        self.username_offset.set(label_width);
+        let room = term_x.saturating_sub(label_width + 1);
+
+        // Scroll no further than needed to show the last column
+        let mut max_scroll_x = widths.len();
+        let mut tail_width = 0;
+        while max_scroll_x > 0 && tail_width + widths[max_scroll_x - 1] <= room {
+            max_scroll_x -= 1;
+            tail_width += widths[max_scroll_x];
+        }
+        let first = self.scroll_x.get().min(max_scroll_x);
+        self.scroll_x.set(first);
+
+        let mut shown_width = 0;
+        let mut shown = 0;
+        for width in &widths[first..] {
+            if shown > 0 && shown_width + width > room {
+                break;
+            }
+            shown_width += width;
+            shown += 1;
+        }
+
+        let board_width = label_width + shown_width + 1;
+        self.x_offset
+            .set(term_x.saturating_sub(board_width).saturating_div(2));
+
+        let board_height = self.board_height();
+        let room = term_y.saturating_sub(1 + self.log.len());
+        let max_scroll_y = board_height.saturating_sub(room);
+        self.scroll_y.set(self.scroll_y.get().min(max_scroll_y));
+        self.y_offset.set(
+            term_y
+                .saturating_div(2)
+                .saturating_sub(board_height / 2)
+                .min(room.saturating_sub(board_height)),
         );
 
-        printer.print((x_offset, y_offset + 2), VERT_RIGHT);
-        printer.print((x_offset, y_offset + 9), VERT_RIGHT);
-        printer.print((x_offset, y_offset + 12), VERT_RIGHT);
-        printer.print((x_offset, y_offset + 19), VERT_RIGHT);
-        printer.print((x_offset, y_offset + 20), VERT_RIGHT);
+        first..first + shown
+    }
 
-        printer.print((x_offset + username_offset, y_offset + 2), VERT_LEFT);
-        printer.print((x_offset + username_offset, y_offset + 9), VERT_LEFT);
-        printer.print((x_offset + username_offset, y_offset + 12), VERT_LEFT);
-        printer.print((x_offset + username_offset, y_offset + 19), VERT_LEFT);
-        printer.print((x_offset + username_offset, y_offset + 20), VERT_LEFT);
+    /// The help line and the log, which stay put while the board scrolls.
+    fn draw_footer(&self, printer: &Printer, columns: &Range<usize>) {
+        let (term_x, term_y) = printer.size.pair();
 
-        //
-        printer.print((x_offset + 10, y_offset + 1), "Name");
+        let help = if let Some(spectator) = &self.spectator {
+            format!(
+                "Spectating {}. Press `{}` to quit.",
+                spectator.source,
+                self.keymap.keys_of(Action::Quit)
+            )
+        } else {
+            format!(
+                "Press `{}` to show the help message.",
+                self.keymap.keys_of(Action::Help)
+            )
+        };
+        printer.print((1, term_y.saturating_sub(1)), &help);
 
-        printer.print((x_offset + 8, y_offset + 3), "Ones   (1)");
-        printer.print((x_offset + 8, y_offset + 4), "Twos   (2)");
-        printer.print((x_offset + 8, y_offset + 5), "Threes (3)");
-        printer.print((x_offset + 8, y_offset + 6), "Fours  (4)");
-        printer.print((x_offset + 8, y_offset + 7), "Fives  (5)");
-        printer.print((x_offset + 8, y_offset + 8), "Sixes  (6)");
+        let player_count = self.players.iter().count();
+        let mut scroll_hint = String::new();
+        if columns.len() < player_count {
+            scroll_hint.push_str(&format!(
+                "Players {}~{}/{} {}",
+                columns.start + 1,
+                columns.end,
+                player_count,
+                self.glyphs.scroll_x
+            ));
+        }
+        if self.board_height() + 1 + self.log.len() > term_y {
+            scroll_hint.push(' ');
+            scroll_hint.push_str(self.glyphs.scroll_y);
+        }
+        let hint_x = term_x.saturating_sub(scroll_hint.width() + 1);
+        if !scroll_hint.is_empty() && hint_x > help.width() {
+            printer.print((hint_x, term_y.saturating_sub(1)), &scroll_hint);
+        }
 
-        printer.print((x_offset + 4, y_offset + 10), "Left to get bonus");
-        printer.print((x_offset + 10, y_offset + 11), "Bonus");
+        for (i, line) in self.log.iter().rev().enumerate() {
+            printer.print((1, term_y.saturating_sub(2 + i)), line);
+        }
+    }
 
-        printer.print((x_offset + 8, y_offset + 13), "Choice     (c)");
-        printer.print((x_offset + 6, y_offset + 14), "Full House   (h)");
-        printer.print((x_offset + 4, y_offset + 15), "Four of a kind (k)");
-        printer.print((x_offset + 4, y_offset + 16), "Small Straight (s)");
-        printer.print((x_offset + 4, y_offset + 17), "Large Straight (l)");
-        printer.print((x_offset + 6, y_offset + 18), "* YACHT *    (y)");
+    /// Rows from the top border to the bottom border, practice dice included.
+    #[inline]
+    fn board_height(&self) -> usize {
+        let grid_height = self.header_height() + self.rows.len() + 1;
+        if self.practice.is_some() {
+            grid_height + PRACTICE_LINES
+        } else {
+            grid_height
+        }
+    }
 
-        printer.print((x_offset + 10, y_offset + 21), "Total");
+    /// Rows holding a horizontal line, relative to the top border
+    fn line_rows(&self) -> impl Iterator<Item = usize> + '_ {
+        let header_height = self.header_height();
+        let separators = self
+            .rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| matches!(row, Row::Separator))
+            .map(move |(i, _)| header_height + i);
+        [0, header_height - 1]
+            .into_iter()
+            .chain(separators)
+            .chain([header_height + self.rows.len()])
     }
-}
 
-impl View for PlayBoard {
-    fn draw(&self, printer: &Printer) {
-        self.draw_init(printer);
+    /// Draws the horizontal lines of a column from `x` to `x + width`.
+    fn draw_horizontal_lines(&self, printer: &Printer, x: usize, width: usize) {
+        let y_offset = self.y_offset.get();
+        for y in self.line_rows() {
+            for i in 1..width {
+                printer.print((x + i, y_offset + y), self.glyphs.horizontal);
+            }
+        }
+    }
 
+    /// Draws a vertical line with `joints` at its top, where it crosses a line and at its bottom.
+    fn draw_vertical_line(&self, printer: &Printer, x: usize, joints: [&str; 3]) {
         let y_offset = self.y_offset.get();
-        let mut offset = self.x_offset.get() + self.username_offset.get();
+        let bottom = self.header_height() + self.rows.len();
+        for y in 0..bottom {
+            printer.print((x, y_offset + y), self.glyphs.vertical);
+        }
+        for y in self.line_rows() {
+            let joint = match y {
+                0 => joints[0],
+                y if y == bottom => joints[2],
+                _ => joints[1],
+            };
+            printer.print((x, y_offset + y), joint);
+        }
+    }
 
-        for (player, scoreboard) in self.players.iter() {
-            let name_offset = str_terminal_len(player);
-
-            // Draw lines
-            for i in 1..name_offset {
-                printer.print((offset + i, y_offset), HORIZONTAL_LINE);
-                printer.print((offset + i, y_offset + 2), HORIZONTAL_LINE);
-                printer.print((offset + i, y_offset + 9), HORIZONTAL_LINE);
-                printer.print((offset + i, y_offset + 12), HORIZONTAL_LINE);
-                printer.print((offset + i, y_offset + 19), HORIZONTAL_LINE);
-                printer.print((offset + i, y_offset + 20), HORIZONTAL_LINE);
-                printer.print((offset + i, y_offset + 22), HORIZONTAL_LINE);
-            }
-            for i in y_offset..(y_offset + 22) {
-                printer.print((offset, i), VERTICAL_LINE);
-                printer.print((offset + name_offset, i), VERTICAL_LINE);
-            }
-            printer.print((offset, y_offset), HORIZ_DOWN);
-            printer.print((offset + name_offset, y_offset), TOP_RIGHT_CORNER);
-            printer.print((offset, y_offset + 22), HORIZ_UP);
-            printer.print((offset + name_offset, y_offset + 22), BOTTOM_RIGHT_CORNER);
-
-            printer.print((offset, y_offset + 2), HORIZ_VERT);
-            printer.print((offset, y_offset + 9), HORIZ_VERT);
-            printer.print((offset, y_offset + 12), HORIZ_VERT);
-            printer.print((offset, y_offset + 19), HORIZ_VERT);
-            printer.print((offset, y_offset + 20), HORIZ_VERT);
-
-            printer.print((offset + name_offset, y_offset + 2), VERT_LEFT);
-            printer.print((offset + name_offset, y_offset + 9), VERT_LEFT);
-            printer.print((offset + name_offset, y_offset + 12), VERT_LEFT);
-            printer.print((offset + name_offset, y_offset + 19), VERT_LEFT);
-            printer.print((offset + name_offset, y_offset + 20), VERT_LEFT);
-
-            // Fill Contents
-            printer.print((offset + 2, self.y_offset.get() + 1), player);
+    /// The frozen label column.
+    fn draw_init(&self, printer: &Printer) {
+        let x_offset = self.x_offset.get();
+        let y_offset = self.y_offset.get();
+        let username_offset = self.username_offset.get();
+        let is_compact = username_offset < LABEL_WIDTH;
 
+        self.draw_horizontal_lines(printer, x_offset, username_offset);
+        self.draw_vertical_line(printer, x_offset, self.glyphs.left_edge);
+        self.draw_vertical_line(
+            printer,
+            x_offset + username_offset.saturating_sub(1),
+            self.glyphs.inner_edge,
+        );
+        self.draw_vertical_line(printer, x_offset + username_offset, self.glyphs.right_edge);
+
+        let name_label = "Name";
+        printer.print(
+            (
+                x_offset + label_indent(name_label, username_offset, is_compact),
+                y_offset + 1,
+            ),
+            name_label,
+        );
+        if let Some(clock) = self.game_clock() {
+            let clock_label = match (clock.is_paused(), is_compact) {
+                (false, false) => "Time left",
+                (false, true) => "Time",
+                (true, false) => "Time left (paused)",
+                (true, true) => "Paused",
+            };
             printer.print(
-                (offset + 2, y_offset + 3),
-                &if let Some(num) = scoreboard.numbers[0] {
-                    format!("{num}")
-                } else {
-                    String::new()
-                },
-            );
-            printer.print(
-                (offset + 2, y_offset + 4),
-                &if let Some(num) = scoreboard.numbers[1] {
-                    format!("{num}")
-                } else {
-                    String::new()
-                },
-            );
-            printer.print(
-                (offset + 2, y_offset + 5),
-                &if let Some(num) = scoreboard.numbers[2] {
-                    format!("{num}")
-                } else {
-                    String::new()
-                },
-            );
-            printer.print(
-                (offset + 2, y_offset + 6),
-                &if let Some(num) = scoreboard.numbers[3] {
-                    format!("{num}")
-                } else {
-                    String::new()
-                },
+                (
+                    x_offset + label_indent(clock_label, username_offset, is_compact),
+                    y_offset + 2,
+                ),
+                clock_label,
             );
-            printer.print(
-                (offset + 2, y_offset + 7),
-                &if let Some(num) = scoreboard.numbers[4] {
-                    format!("{num}")
+        }
+        for (i, row) in self.rows.iter().enumerate() {
+            let Some((label, keys)) = row.label(is_compact, &self.keymap) else {
+                continue;
+            };
+            let with_keys = keys.map(|keys| {
+                if is_compact {
+                    format!("{label:<8}({keys})")
                 } else {
-                    String::new()
-                },
-            );
+                    format!("{label:<15}({keys})")
+                }
+            });
+            // Keys too long for the column are left out rather than cut
+            let room = username_offset.saturating_sub(if is_compact { 3 } else { 4 });
+            let label = with_keys
+                .filter(|with_keys| with_keys.width() <= room)
+                .unwrap_or_else(|| label.to_string());
             printer.print(
-                (offset + 2, y_offset + 8),
-                &if let Some(num) = scoreboard.numbers[5] {
-                    format!("{num}")
-                } else {
-                    String::new()
-                },
+                (
+                    x_offset + label_indent(&label, username_offset, is_compact),
+                    y_offset + self.header_height() + i,
+                ),
+                &label,
             );
+        }
+    }
+}
 
-            printer.print(
-                (offset + 2, y_offset + 10),
-                &format!("{}", scoreboard.left_to_get_bonus),
-            );
-            printer.print(
-                (offset + 2, y_offset + 11),
-                &format!("{}", scoreboard.bonus),
-            );
+impl View for PlayBoard {
+    fn draw(&self, printer: &Printer) {
+        let columns = self.layout(printer.size);
+        self.draw_footer(printer, &columns);
 
-            printer.print(
-                (offset + 2, y_offset + 13),
-                &if let Some(num) = scoreboard.choice {
-                    format!("{num}")
-                } else {
-                    String::new()
-                },
-            );
-            printer.print(
-                (offset + 2, y_offset + 14),
-                &if let Some(num) = scoreboard.full_house {
-                    format!("{num}")
-                } else {
-                    String::new()
-                },
-            );
-            printer.print(
-                (offset + 2, y_offset + 15),
-                &if let Some(num) = scoreboard.four_of_kind {
-                    format!("{num}")
-                } else {
-                    String::new()
-                },
-            );
-            printer.print(
-                (offset + 2, y_offset + 16),
-                &if let Some(num) = scoreboard.small_straight {
-                    format!("{num}")
-                } else {
-                    String::new()
-                },
-            );
-            printer.print(
-                (offset + 2, y_offset + 17),
-                &if let Some(num) = scoreboard.large_straight {
-                    format!("{num}")
+        let room = printer.size.y.saturating_sub(1 + self.log.len());
+        let printer = printer
+            .cropped((printer.size.x, room))
+            .content_offset((0, self.scroll_y.get()));
+        self.draw_init(&printer);
+
+        let y_offset = self.y_offset.get();
+        let header_height = self.header_height();
+        let mut offset = self.x_offset.get() + self.username_offset.get();
+
+        for (player, scoreboard) in self.players.iter().skip(columns.start).take(columns.len()) {
+            let name_offset = self.column_width(player);
+            let text_offset = (name_offset - self.content_width(player)) / 2 + 2;
+
+            self.draw_horizontal_lines(&printer, offset, name_offset);
+            self.draw_vertical_line(&printer, offset, self.glyphs.inner_edge);
+            self.draw_vertical_line(&printer, offset + name_offset, self.glyphs.right_edge);
+
+            let rank = self.players.rank_of(player).unwrap_or(0);
+            let is_leader = rank == 1 && scoreboard.total_score > 0;
+            let is_current = self.players.current_player() == Some(player);
+
+            let mut name_style = Style::none();
+            if is_leader {
+                name_style = name_style.combine(self.styles.leader);
+            }
+            if is_current {
+                name_style = name_style.combine(self.styles.current);
+            }
+            printer.with_style(name_style, |printer| {
+                printer.print((offset + text_offset, y_offset + 1), player);
+            });
+            if let Some(clock) = self.game_clock() {
+                let time_left = clock.time_left(player);
+                let clock_style = if time_left.is_zero() {
+                    self.styles.out_of_reach
+                } else if is_current {
+                    self.styles.current
                 } else {
-                    String::new()
-                },
-            );
+                    Style::none()
+                };
+                printer.with_style(clock_style, |printer| {
+                    printer.print(
+                        (offset + text_offset, y_offset + 2),
+                        &clock::format_time(time_left),
+                    );
+                });
+            }
+
+            for (i, row) in self.rows.iter().enumerate() {
+                if let Some((text, shade)) =
+                    row.value(&self.players, player, scoreboard, self.glyphs)
+                {
+                    printer.with_style(self.style_of(shade), |printer| {
+                        printer.print((offset + text_offset, y_offset + header_height + i), &text);
+                    });
+                }
+            }
+
+            offset += name_offset;
+        }
+
+        if let Some(tray) = &self.practice {
+            let x_offset = self.x_offset.get();
+            let tray_y = y_offset + header_height + self.rows.len() + 1;
             printer.print(
-                (offset + 2, y_offset + 18),
-                &if let Some(num) = scoreboard.yacht {
-                    format!("{num}")
-                } else {
-                    String::new()
-                },
+                (x_offset, tray_y),
+                &tray.describe(&self.keymap.keys_of(Action::Roll)),
             );
-
             printer.print(
-                (offset + 2, y_offset + 21),
-                &format!("{}", scoreboard.total_score),
+                (x_offset, tray_y + 1),
+                &format!(
+                    "{}: roll    1 ~ 5: hold a die    Enter: score    {}: leave practice",
+                    self.keymap.keys_of(Action::Roll),
+                    self.keymap.keys_of(Action::Practice)
+                ),
             );
-
-            offset += str_terminal_len(player);
         }
     }
 
@@ -327,83 +576,272 @@ impl View for PlayBoard {
     }
 
     fn on_event(&mut self, event: Event) -> EventResult {
-        match event {
-            Event::Char('q') => EventResult::with_cb(|siv| siv.quit()),
-            Event::Char('?') => EventResult::with_cb(|siv| siv.add_layer(infobox::InfoBox)),
-            Event::Char('C') => EventResult::with_cb_once(|siv| {
+        // The dice tray keys are fixed, and only while no chord is half typed
+        if let (Some(tray), true) = (self.practice.as_mut(), self.pending_keys.is_empty()) {
+            if self.spectator.is_none() && !self.players.is_game_over() {
+                match event {
+                    Event::Char(ch @ '1'..='5') => {
+                        tray.toggle_hold(ch as usize - '1' as usize);
+                        return EventResult::Consumed(None);
+                    }
+                    Event::Key(Key::Enter) => {
+                        return EventResult::with_cb_once(practice::choose_category)
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        self.pending_keys.push(event);
+        match self.keymap.lookup(&self.pending_keys) {
+            Lookup::Action(action) => {
+                self.pending_keys.clear();
+                self.run_action(action)
+            }
+            Lookup::Pending => EventResult::Consumed(None),
+            Lookup::Unbound => {
+                let was_chord = self.pending_keys.len() > 1;
+                self.pending_keys.clear();
+                if was_chord {
+                    EventResult::Consumed(None)
+                } else {
+                    EventResult::Ignored
+                }
+            }
+        }
+    }
+}
+
+impl PlayBoard {
+    fn run_action(&mut self, action: Action) -> EventResult {
+        // `draw` clamps these to what there is to scroll
+        match action {
+            Action::ScrollLeft => {
+                self.scroll_x.set(self.scroll_x.get().saturating_sub(1));
+                return EventResult::Consumed(None);
+            }
+            Action::ScrollRight => {
+                self.scroll_x.set(self.scroll_x.get() + 1);
+                return EventResult::Consumed(None);
+            }
+            Action::ScrollUp => {
+                self.scroll_y.set(self.scroll_y.get().saturating_sub(1));
+                return EventResult::Consumed(None);
+            }
+            Action::ScrollDown => {
+                self.scroll_y.set(self.scroll_y.get() + 1);
+                return EventResult::Consumed(None);
+            }
+            _ => {}
+        }
+
+        if self.spectator.is_some() {
+            return match action {
+                Action::Quit => EventResult::with_cb(|siv| siv.quit()),
+                _ => EventResult::Ignored,
+            };
+        }
+
+        if let (Action::Roll, Some(tray)) = (action, self.practice.as_mut()) {
+            if !self.players.is_game_over() {
+                tray.roll(&mut self.rng);
+            }
+            return EventResult::Consumed(None);
+        }
+
+        if let (true, Err(err)) = (action.is_edit(), self.may_edit()) {
+            return EventResult::with_cb_once(move |siv| show_error(siv, err));
+        }
+        let changes_card = action != Action::Practice && (action.is_edit() || action.is_score());
+        if let (true, Err(err)) = (changes_card, self.may_change_card()) {
+            return EventResult::with_cb_once(move |siv| show_error(siv, err));
+        }
+
+        match action {
+            Action::Quit => EventResult::with_cb(|siv| siv.quit()),
+            Action::CommandPalette => {
+                let items = palette::palette_items(&self.keymap);
+                EventResult::with_cb_once(move |siv| palette::show_palette(siv, items))
+            }
+            Action::Help => {
+                let entries = infobox::help_entries(&self.keymap);
+                EventResult::with_cb_once(move |siv| infobox::show_help(siv, entries))
+            }
+            Action::ClearScores => EventResult::with_cb_once(|siv| {
                 siv.call_on_name("playboard", |play_board: &mut PlayBoard| {
                     play_board.players.clear_score();
+                    play_board.players_changed();
                 });
+                schedule_bot_turn(siv);
+            }),
+            Action::Practice if self.network.is_some() => EventResult::with_cb_once(|siv| {
+                show_error(
+                    siv,
+                    "Practice is not available in a network game".to_string(),
+                )
+            }),
+            Action::Practice if self.practice.is_some() => {
+                self.practice = None;
+                EventResult::Consumed(None)
+            }
+            Action::Practice if self.players.is_empty() => {
+                self.start_practice();
+                EventResult::Consumed(None)
+            }
+            Action::Practice => EventResult::with_cb_once(|siv| {
+                siv.add_layer(
+                    Dialog::text("Start a practice game?\nThe current board will be cleared.")
+                        .title("Practice")
+                        .button("Ok", |s| {
+                            s.call_on_name("playboard", PlayBoard::start_practice);
+                            s.pop_layer();
+                        })
+                        .button("Cancel", |s| {
+                            s.pop_layer();
+                        }),
+                );
             }),
-            Event::Char('a') => make_popup!(
+            Action::HighScores => EventResult::with_cb_once(practice::show_high_scores),
+            Action::NewMatch => EventResult::with_cb_once(standings::show_new_match),
+            Action::MatchStandings => EventResult::with_cb_once(standings::show_standings),
+            Action::Tournament => EventResult::with_cb_once(bracket::show_tournament),
+            Action::PauseClock => match self.toggle_clock() {
+                Ok(()) => EventResult::Consumed(None),
+                Err(err) => EventResult::with_cb_once(move |siv| show_error(siv, err)),
+            },
+            Action::Undo => match self.undo() {
+                Ok(_) => EventResult::with_cb_once(schedule_bot_turn),
+                Err(err) => EventResult::with_cb_once(move |siv| show_error(siv, err)),
+            },
+            Action::AddBot => EventResult::with_cb_once(|siv| {
+                let mut difficulties = SelectView::new();
+                for difficulty in Difficulty::ALL {
+                    difficulties.add_item(difficulty.name(), difficulty);
+                }
+
+                siv.add_layer(
+                    Dialog::new()
+                        .title("Add Computer Player")
+                        .content(
+                            LinearLayout::vertical()
+                                .child(TextView::new("Give a player name to add"))
+                                .child(EditView::new().with_name("add_bot_name"))
+                                .child(TextView::new("Choose the difficulty"))
+                                .child(difficulties.with_name("add_bot_difficulty")),
+                        )
+                        .button("Ok", add_bot)
+                        .button("Cancel", |s| {
+                            s.pop_layer();
+                        }),
+                );
+            }),
+            Action::AddPlayer => make_popup!(
                 "add_player_name",
                 "Add Player",
                 "Give a player name to add",
                 add_player
             ),
-            Event::Char('d') => make_popup!(
-                "del_player_name",
-                "Delete Player",
-                "Give a player name to remove",
-                delete_player
-            ),
-            Event::Char('1') => score_event!("Ones", ScoreInput::Ones(None)),
-            Event::Char('2') => score_event!("Twos", ScoreInput::Twos(None)),
-            Event::Char('3') => score_event!("Threes", ScoreInput::Threes(None)),
-            Event::Char('4') => score_event!("Fours", ScoreInput::Fours(None)),
-            Event::Char('5') => score_event!("Fives", ScoreInput::Fives(None)),
-            Event::Char('6') => score_event!("Sixes", ScoreInput::Sixes(None)),
-            Event::Char('c') => score_event!("Choice", ScoreInput::Choice(None)),
-            Event::Char('h') => score_event!("Full House", ScoreInput::FullHouse(None)),
-            Event::Char('k') => score_event!("Four of a Kind", ScoreInput::FourOfKind(None)),
-            Event::Char('s') => make_popup!(
-                "update_player_score",
-                "Small Straight",
-                "Input the player name",
-                move |s| update_player_score(s, ScoreInput::SmallStraight(true))
-            ),
-            Event::Char('l') => make_popup!(
-                "update_player_score",
-                "Large Straight",
-                "Input the player name",
-                move |s| update_player_score(s, ScoreInput::LargeStraight(true))
-            ),
-            Event::Char('y') => make_popup!(
-                "update_player_score",
-                "* YACHT *",
-                "Input the player name",
-                move |s| update_player_score(s, ScoreInput::Yacht(true))
-            ),
-            Event::Char('S') => make_popup!(
-                "update_player_score",
-                "Small Straight",
-                "Input the player name",
-                move |s| update_player_score(s, ScoreInput::SmallStraight(false))
-            ),
-            Event::Char('L') => make_popup!(
-                "update_player_score",
-                "Large Straight",
-                "Input the player name",
-                move |s| update_player_score(s, ScoreInput::LargeStraight(false))
-            ),
-            Event::Char('Y') => make_popup!(
-                "update_player_score",
-                "* YACHT *",
-                "Input the player name",
-                move |s| update_player_score(s, ScoreInput::Yacht(false))
+            Action::DeletePlayer => player_event!("Delete Player", delete_player),
+            Action::RenamePlayer => player_event!("Rename Player", ask_new_name),
+            Action::AddTeam => EventResult::with_cb_once(|siv| {
+                let mut modes = SelectView::new();
+                for mode in TeamMode::ALL {
+                    modes.add_item(mode.name(), mode);
+                }
+
+                siv.add_layer(
+                    Dialog::new()
+                        .title("Add Team")
+                        .content(
+                            LinearLayout::vertical()
+                                .child(TextView::new("Give a team name"))
+                                .child(EditView::new().with_name("add_team_name"))
+                                .child(TextView::new("Give the members, separated by commas"))
+                                .child(EditView::new().with_name("add_team_members"))
+                                .child(TextView::new("Choose how the team plays"))
+                                .child(modes.with_name("add_team_mode")),
+                        )
+                        .button("Ok", add_team)
+                        .button("Cancel", |s| {
+                            s.pop_layer();
+                        }),
+                );
+            }),
+            Action::DeleteTeam => make_popup!(
+                "del_team_name",
+                "Delete Team",
+                "Give a team name to break up",
+                delete_team
             ),
-            Event::CtrlChar('s') => make_popup!(
+            Action::Ones => score_event!("Ones", ScoreInput::Ones(None)),
+            Action::Twos => score_event!("Twos", ScoreInput::Twos(None)),
+            Action::Threes => score_event!("Threes", ScoreInput::Threes(None)),
+            Action::Fours => score_event!("Fours", ScoreInput::Fours(None)),
+            Action::Fives => score_event!("Fives", ScoreInput::Fives(None)),
+            Action::Sixes => score_event!("Sixes", ScoreInput::Sixes(None)),
+            Action::Choice => score_event!("Choice", ScoreInput::Choice(None)),
+            Action::FullHouse => score_event!("Full House", ScoreInput::FullHouse(None)),
+            Action::FourOfKind => score_event!("Four of a Kind", ScoreInput::FourOfKind(None)),
+            Action::SmallStraight => player_event!("Small Straight", move |s, name| {
+                update_player_score(s, name, ScoreInput::SmallStraight(true))
+            }),
+            Action::LargeStraight => player_event!("Large Straight", move |s, name| {
+                update_player_score(s, name, ScoreInput::LargeStraight(true))
+            }),
+            Action::Yacht => player_event!("* YACHT *", move |s, name| {
+                update_player_score(s, name, ScoreInput::Yacht(true))
+            }),
+            Action::SmallStraightMiss => player_event!("Small Straight", move |s, name| {
+                update_player_score(s, name, ScoreInput::SmallStraight(false))
+            }),
+            Action::LargeStraightMiss => player_event!("Large Straight", move |s, name| {
+                update_player_score(s, name, ScoreInput::LargeStraight(false))
+            }),
+            Action::YachtMiss => player_event!("* YACHT *", move |s, name| {
+                update_player_score(s, name, ScoreInput::Yacht(false))
+            }),
+            Action::Hint => player_event!("Hint", ask_hint_dice),
+            Action::Save => make_popup!(
                 "save_data_filename",
                 "Save as",
                 "Give a filename to save",
                 save_data
             ),
-            Event::CtrlChar('l') => make_popup!(
-                "load_data_path",
-                "Load as",
-                "Give a path of saved JSON file",
-                load_data
+            Action::Export => make_popup!(
+                "export_filename",
+                "Export as",
+                "Give a filename for the CSV file",
+                export_data
             ),
+            Action::Load => EventResult::with_cb_once(|siv| {
+                siv.add_layer(
+                    Dialog::new()
+                        .title("Load as")
+                        .content(
+                            LinearLayout::vertical()
+                                .child(TextView::new("Give a path of saved JSON file"))
+                                .child(
+                                    OnEventView::new(
+                                        EditView::new()
+                                            .on_submit(|s, _| load_data(s))
+                                            .with_name("load_data_path"),
+                                    )
+                                    .on_event(Key::Esc, |s| {
+                                        s.pop_layer();
+                                    }),
+                                )
+                                .child(
+                                    LinearLayout::horizontal()
+                                        .child(Checkbox::new().with_name("load_data_watch"))
+                                        .child(TextView::new(" Reload when the file changes")),
+                                ),
+                        )
+                        .button("Ok", load_data)
+                        .button("Cancel", |s| {
+                            s.pop_layer();
+                        }),
+                );
+            }),
             _ => EventResult::Ignored,
         }
     }
@@ -413,97 +851,524 @@ fn add_player(siv: &mut Cursive) {
     let player_name = siv.call_on_name("add_player_name", |view: &mut EditView| view.get_content());
 
     if let Some(name) = player_name {
-        siv.call_on_name("playboard", |play_board: &mut PlayBoard| {
-            play_board.players.add_player(&name);
-            let width = play_board.width.get();
-            play_board.width.set(width + str_terminal_len(&name));
-        });
+        let result = siv
+            .call_on_name("playboard", |play_board: &mut PlayBoard| {
+                play_board.players.add_player(name.trim())?;
+                play_board.players_changed();
+                Ok(())
+            })
+            .expect("`playboard` must exists");
+        // Leaves the dialog open so the name can be fixed
+        if let Err(err) = result {
+            show_error(siv, err);
+            return;
+        }
     }
 
     siv.pop_layer();
+    schedule_bot_turn(siv);
 }
 
-fn delete_player(siv: &mut Cursive) {
-    let player_name = siv.call_on_name("del_player_name", |view: &mut EditView| view.get_content());
+fn add_bot(siv: &mut Cursive) {
+    let player_name = siv.call_on_name("add_bot_name", |view: &mut EditView| view.get_content());
+    let difficulty = siv
+        .call_on_name("add_bot_difficulty", |view: &mut SelectView<Difficulty>| {
+            view.selection()
+        })
+        .flatten();
 
-    if let Some(name) = player_name {
-        let well_removed = siv
+    if let (Some(name), Some(difficulty)) = (player_name, difficulty) {
+        let result = siv
             .call_on_name("playboard", |play_board: &mut PlayBoard| {
-                let width = play_board.width.get();
-                play_board
-                    .width
-                    .set(width.saturating_sub(str_terminal_len(&name)));
-                play_board.players.del_player(&name)
+                play_board.players.add_bot(name.trim(), *difficulty)?;
+                play_board.players_changed();
+                Ok(())
             })
             .expect("`playboard` must exists");
+        if let Err(err) = result {
+            show_error(siv, err);
+            return;
+        }
+    }
 
-        if !well_removed {
-            siv.add_layer(
-                Dialog::new().title("Cannot Delete Player").content(
-                    LinearLayout::vertical()
-                        .child(TextView::new("There is no player to remove from the list"))
-                        .child(Button::new("Ok", |s| {
-                            s.pop_layer();
-                        })),
-                ),
-            );
+    siv.pop_layer();
+    schedule_bot_turn(siv);
+}
+
+/// Asks for the new name of `name`, starting from the current one.
+fn ask_new_name(siv: &mut Cursive, name: &str) {
+    let old = name.to_string();
+    let on_submit = move |s: &mut Cursive, new: &str| rename_player(s, &old, new.trim());
+    siv.add_layer(
+        OnEventView::new(
+            Dialog::new().title("Rename Player").content(
+                LinearLayout::vertical()
+                    .child(TextView::new(format!("Give a new name for `{name}`")))
+                    .child(
+                        EditView::new()
+                            .content(name)
+                            .on_submit(on_submit)
+                            .with_name("rename_player_name"),
+                    ),
+            ),
+        )
+        .on_event(Key::Esc, |s| {
+            s.pop_layer();
+        }),
+    );
+}
+
+fn rename_player(siv: &mut Cursive, old: &str, new: &str) {
+    let result = siv
+        .call_on_name("playboard", |play_board: &mut PlayBoard| {
+            if play_board.is_remote_player(old) {
+                return Err(format!("`{old}` is connected and keeps their name"));
+            }
+            play_board.players.rename_player(old, new)?;
+            play_board.players_changed();
+            Ok(())
+        })
+        .expect("`playboard` must exists");
+
+    match result {
+        Ok(()) => {
+            siv.pop_layer();
+        }
+        Err(err) => show_error(siv, err),
+    }
+}
+
+fn add_team(siv: &mut Cursive) {
+    let team_name = siv.call_on_name("add_team_name", |view: &mut EditView| view.get_content());
+    let members = siv.call_on_name("add_team_members", |view: &mut EditView| view.get_content());
+    let mode = siv
+        .call_on_name("add_team_mode", |view: &mut SelectView<TeamMode>| {
+            view.selection()
+        })
+        .flatten();
+
+    if let (Some(name), Some(members), Some(mode)) = (team_name, members, mode) {
+        let members = members.split(',').map(str::trim).collect::<Vec<_>>();
+        let result = siv
+            .call_on_name("playboard", |play_board: &mut PlayBoard| {
+                play_board.players.add_team(name.trim(), &members, *mode)?;
+                play_board.players_changed();
+                Ok(())
+            })
+            .expect("`playboard` must exists");
+        if let Err(err) = result {
+            show_error(siv, err);
+            return;
+        }
+    }
+
+    siv.pop_layer();
+    schedule_bot_turn(siv);
+}
+
+fn delete_team(siv: &mut Cursive) {
+    let team_name = siv.call_on_name("del_team_name", |view: &mut EditView| view.get_content());
+
+    if let Some(name) = team_name {
+        let result = siv
+            .call_on_name("playboard", |play_board: &mut PlayBoard| {
+                play_board.players.del_team(name.trim())?;
+                play_board.players_changed();
+                Ok(())
+            })
+            .expect("`playboard` must exists");
+        if let Err(err) = result {
+            show_error(siv, err);
+            return;
         }
     }
 
     siv.pop_layer();
+    schedule_bot_turn(siv);
 }
 
-fn get_score_input(siv: &mut Cursive, title: &str, score_input: ScoreInput) {
+fn delete_player(siv: &mut Cursive, name: &str) {
+    let well_removed = siv
+        .call_on_name("playboard", |play_board: &mut PlayBoard| {
+            let well_removed = play_board.players.del_player(name);
+            play_board.players_changed();
+            well_removed
+        })
+        .expect("`playboard` must exists");
+
+    if !well_removed {
+        show_error(siv, format!("There is no player named `{name}`"));
+        return;
+    }
+    schedule_bot_turn(siv);
+}
+
+fn get_score_input(siv: &mut Cursive, title: &'static str, score_input: ScoreInput) {
     let score_str = siv.call_on_name("get_score", |view: &mut EditView| view.get_content());
 
     if let Some(score) = score_str {
+        siv.pop_layer();
         let Ok(score) = score.trim().parse::<u16>() else {
-            siv.pop_layer();
+            show_error(siv, format!("`{}` is not a score", score.trim()));
             return;
         };
-        siv.pop_layer();
-        siv.add_layer(
-            Dialog::new().title(title).content(
-                LinearLayout::vertical()
-                    .child(TextView::new("Input the player name"))
-                    .child(
-                        OnEventView::new(
-                            EditView::new()
-                                .on_submit(move |s, _| {
-                                    update_player_score(s, score_input.inject(score))
-                                })
-                                .with_name("update_player_score"),
-                        )
-                        .on_event(Key::Esc, |s| {
-                            s.pop_layer();
-                        }),
-                    )
-                    .child(
-                        LinearLayout::horizontal()
-                            .child(Button::new("Ok", move |s| {
-                                update_player_score(s, score_input.inject(score))
-                            }))
-                            .child(Button::new("Cancel", |s| {
-                                s.pop_layer();
-                            })),
-                    ),
-            ),
-        );
+        let category = score_input.category();
+        if !category.is_possible(score) {
+            show_error(
+                siv,
+                format!("{score} is not a possible score at {}", category.name()),
+            );
+            return;
+        }
+        player_picker::choose_player(siv, title, move |s, name| {
+            update_player_score(s, name, score_input.inject(score))
+        });
     }
 }
 
-fn update_player_score(siv: &mut Cursive, score: ScoreInput) {
-    let player_name = siv.call_on_name("update_player_score", |view: &mut EditView| {
-        view.get_content()
+fn update_player_score(siv: &mut Cursive, name: &str, score: ScoreInput) {
+    let result = siv.call_on_name("playboard", |play_board: &mut PlayBoard| {
+        play_board.record_score(name, score)
     });
 
-    if let Some(name) = player_name {
-        siv.call_on_name("playboard", |play_board: &mut PlayBoard| {
-            play_board.players.calculate_score(&name, score);
+    match result {
+        Some(Ok(Some(was_over))) => finish_turn(siv, was_over),
+        Some(Err(err)) => show_error(siv, err),
+        _ => {}
+    }
+}
+
+/// Announces the winner if that score ended the game, otherwise lets a bot move.
+fn finish_turn(siv: &mut Cursive, was_over: bool) {
+    let is_finished = siv
+        .call_on_name("playboard", |play_board: &mut PlayBoard| {
+            !was_over && play_board.players.is_game_over()
+        })
+        .unwrap_or(false);
+
+    let is_practice = siv
+        .call_on_name("playboard", |play_board: &mut PlayBoard| {
+            play_board.practice.is_some()
+        })
+        .unwrap_or(false);
+
+    if is_finished && is_practice {
+        practice::finish_game(siv);
+    } else if is_finished {
+        show_final_results(siv);
+        bracket::record_result(siv);
+    } else {
+        schedule_bot_turn(siv);
+    }
+}
+
+/// Plays the next bot turn after a short pause, so that every move can be followed.
+fn schedule_bot_turn(siv: &mut Cursive) {
+    let should_play = siv
+        .call_on_name("playboard", |play_board: &mut PlayBoard| {
+            // Bots of a network game are played by the host
+            let is_bot_turn = !play_board.is_client()
+                && play_board
+                    .players
+                    .current_player()
+                    .is_some_and(|name| play_board.players.bot_difficulty(name).is_some());
+            let should_play = is_bot_turn && !play_board.bot_pending;
+            play_board.bot_pending |= should_play;
+            should_play
+        })
+        .unwrap_or(false);
+
+    if should_play {
+        let cb_sink = siv.cb_sink().clone();
+        thread::spawn(move || {
+            thread::sleep(BOT_DELAY);
+            let _ = cb_sink.send(Box::new(play_bot_turn));
         });
     }
+}
+
+fn play_bot_turn(siv: &mut Cursive) {
+    let was_over = siv
+        .call_on_name("playboard", |play_board: &mut PlayBoard| {
+            play_board.bot_pending = false;
+
+            let was_over = play_board.players.is_game_over();
+            let name = play_board.players.current_player()?.clone();
+            let difficulty = play_board.players.bot_difficulty(&name)?;
+            let scoreboard = play_board.players.get_player_score(&name)?;
+            let turn = bot::play_turn(scoreboard, difficulty, &mut play_board.rng)?;
+
+            play_board
+                .players
+                .calculate_score(&name, turn.category.to_score_input(turn.dice()));
+            let line = describe_turn(&name, difficulty, &turn, play_board.glyphs);
+            play_board.push_log(line);
+            play_board.players_changed();
+
+            Some(was_over)
+        })
+        .flatten();
+
+    if let Some(was_over) = was_over {
+        finish_turn(siv, was_over);
+    }
+}
+
+fn describe_turn(name: &str, difficulty: Difficulty, turn: &Turn, glyphs: &Glyphs) -> String {
+    let rolls = turn
+        .rolls
+        .iter()
+        .map(|dice| {
+            dice.iter()
+                .map(|die| die.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join(glyphs.arrow);
+
+    format!(
+        "{name} [{}]: {rolls}, scored {} at {}",
+        difficulty.name(),
+        turn.category.score(turn.dice()),
+        turn.category.name()
+    )
+}
+
+fn show_final_results(siv: &mut Cursive) {
+    let Some((results, is_match_on, is_tournament_game)) =
+        siv.call_on_name("playboard", |play_board: &mut PlayBoard| {
+            let standings = play_board.players.standings();
+            let name_width = standings
+                .iter()
+                .map(|(_, name, _)| UnicodeWidthStr::width_cjk(name.as_str()))
+                .max()
+                .unwrap_or(0);
+
+            let mut results = String::new();
+            for (rank, name, scoreboard) in standings {
+                let padding = name_width - UnicodeWidthStr::width_cjk(name.as_str());
+                results.push_str(&format!(
+                    "{:>5}  {name}{:padding$}  {:>3}\n",
+                    ordinal(rank),
+                    "",
+                    scoreboard.total_score
+                ));
+            }
+
+            let winners = play_board
+                .players
+                .standings()
+                .into_iter()
+                .filter(|(rank, _, _)| *rank == 1)
+                .map(|(_, name, _)| name.as_str())
+                .collect::<Vec<_>>();
+            let headline = if winners.len() > 1 {
+                format!("It's a tie between {}!", winners.join(", "))
+            } else {
+                format!("{} wins!", winners.join(""))
+            };
+
+            let mut teams = play_board
+                .players
+                .teams()
+                .iter()
+                .map(|team| (play_board.players.team_total(team), team.name.as_str()))
+                .collect::<Vec<_>>();
+            teams.sort_by(|lhs, rhs| rhs.0.cmp(&lhs.0).then_with(|| lhs.1.cmp(rhs.1)));
+            let team_width = teams
+                .iter()
+                .map(|(_, name)| UnicodeWidthStr::width_cjk(*name))
+                .max()
+                .unwrap_or(0);
+            let mut team_results = String::new();
+            for &(total, name) in &teams {
+                let rank = 1 + teams.iter().filter(|(other, _)| *other > total).count();
+                let padding = team_width - UnicodeWidthStr::width_cjk(name);
+                team_results.push_str(&format!(
+                    "{:>5}  {name}{:padding$}  {total:>3}\n",
+                    ordinal(rank),
+                    ""
+                ));
+            }
+
+            let mut results = format!("{headline}\n\n{results}");
+            if !team_results.is_empty() {
+                results.push_str(&format!("\nTeams\n{team_results}"));
+            }
+            let series = play_board.series.as_ref();
+            if let Some(series) = series {
+                let standings = standings::standings_text(series, &play_board.players);
+                results.push_str(&format!("\n{standings}"));
+            }
+            let is_match_on = series.is_some_and(|series| !series.is_over(&play_board.players));
+
+            (results, is_match_on, play_board.is_tournament_game())
+        })
+    else {
+        return;
+    };
+
+    let dialog = Dialog::new()
+        .title("Final Results")
+        .content(TextView::new(results));
+    let dialog = if is_match_on {
+        dialog.button("Next game", |s| {
+            s.pop_layer();
+            standings::next_game(s);
+        })
+    } else if is_tournament_game {
+        // The game counts once; the tournament dialog offers the next one
+        dialog
+    } else {
+        dialog.button("Rematch", |s| {
+            let result = s
+                .call_on_name("playboard", |play_board: &mut PlayBoard| {
+                    play_board.may_edit()?;
+                    play_board.players.clear_score();
+                    // A decided match starts over
+                    if let Some(series) = &mut play_board.series {
+                        *series = Series::new(series.format);
+                    }
+                    play_board.players_changed();
+                    Ok(())
+                })
+                .unwrap_or(Ok(()));
+            s.pop_layer();
+            match result {
+                Ok(()) => schedule_bot_turn(s),
+                Err(err) => show_error(s, err),
+            }
+        })
+    };
+    siv.add_layer(dialog.button("Close", |s| {
+        s.pop_layer();
+    }));
+}
+
+/// Asks for the dice of `name`, starting from the practice tray when it holds a roll.
+fn ask_hint_dice(siv: &mut Cursive, name: &str) {
+    let (dice, rerolls) = siv
+        .call_on_name("playboard", |play_board: &mut PlayBoard| {
+            let tray = play_board.practice.as_ref()?;
+            Some((tray.dice?, tray.rerolls_left))
+        })
+        .flatten()
+        .map(|(dice, rerolls)| {
+            let dice = dice
+                .iter()
+                .map(|die| die.to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            (dice, rerolls)
+        })
+        .unwrap_or((String::new(), MAX_REROLLS));
+
+    let name = name.to_string();
+    siv.add_layer(
+        Dialog::new()
+            .title("Hint")
+            .content(
+                LinearLayout::vertical()
+                    .child(TextView::new(format!(
+                        "Input the dice of `{name}` (e.g. 3 3 5 1 6)"
+                    )))
+                    .child(EditView::new().content(dice).with_name("hint_dice"))
+                    .child(TextView::new(format!("Rerolls left (0 ~ {MAX_REROLLS})")))
+                    .child(
+                        EditView::new()
+                            .content(rerolls.to_string())
+                            .with_name("hint_rerolls"),
+                    ),
+            )
+            .button("Ok", move |s| show_hint(s, &name))
+            .button("Cancel", |s| {
+                s.pop_layer();
+            }),
+    );
+}
+
+fn show_hint(siv: &mut Cursive, name: &str) {
+    let get_content = |siv: &mut Cursive, name: &str| {
+        siv.call_on_name(name, |view: &mut EditView| view.get_content())
+            .map(|content| content.to_string())
+            .unwrap_or_default()
+    };
+    let dice = get_content(siv, "hint_dice");
+    let rerolls = get_content(siv, "hint_rerolls");
 
     siv.pop_layer();
+
+    let dice = dice
+        .chars()
+        .filter(|ch| !ch.is_whitespace() && *ch != ',')
+        .map(|ch| {
+            ch.to_digit(10)
+                .filter(|d| (1..=6).contains(d))
+                .map(|d| d as u8)
+        })
+        .collect::<Option<Vec<_>>>()
+        .and_then(|dice| Dice::try_from(dice).ok());
+    let rerolls = rerolls
+        .trim()
+        .parse::<u8>()
+        .ok()
+        .filter(|rerolls| *rerolls <= MAX_REROLLS);
+
+    let message = match (dice, rerolls) {
+        (Some(dice), Some(rerolls)) => siv
+            .call_on_name("playboard", |play_board: &mut PlayBoard| {
+                let Some(scoreboard) = play_board.players.get_player_score(&name.to_string())
+                else {
+                    return format!("There is no player named `{name}`");
+                };
+                match Advisor::new(scoreboard).advise(dice, rerolls) {
+                    Some(hint) => describe_hint(&dice, &hint),
+                    None => format!("There is nothing left to score for `{name}`"),
+                }
+            })
+            .unwrap_or_default(),
+        _ => "The dice must be five numbers from 1 to 6,\nand the rerolls a number from 0 to 2"
+            .to_string(),
+    };
+
+    siv.add_layer(
+        Dialog::new()
+            .title("Hint")
+            .content(TextView::new(message))
+            .button("Ok", |s| {
+                s.pop_layer();
+            }),
+    );
+}
+
+fn describe_hint(dice: &Dice, hint: &Hint) -> String {
+    let score_now = format!(
+        "Score {} for {} point(s)",
+        hint.category.name(),
+        hint.score_now
+    );
+
+    if hint.should_stop {
+        return format!("{score_now} now.");
+    }
+
+    let hold = dice
+        .iter()
+        .zip(hint.hold)
+        .map(|(die, held)| {
+            if held {
+                die.to_string()
+            } else {
+                "_".to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(
+        "Hold  {hold}  and reroll the rest.\nRating: {:.1} (expected points, with a share of\nthe bonus for Ones ~ Sixes while it is in reach)\n\nIf you stop here: {score_now}.",
+        hint.expected
+    )
 }
 
 fn save_data(siv: &mut Cursive) {
@@ -511,80 +1376,131 @@ fn save_data(siv: &mut Cursive) {
         view.get_content()
     });
 
+    let mut result = Ok(());
     if let Some(filename) = filename {
         let file = fs::File::create(&*filename).map_err(|err| err.to_string());
-        let result = siv.call_on_name("playboard", move |play_board: &mut PlayBoard| {
-            file.and_then(|f| {
-                let buf_writer = BufWriter::new(f);
-                serde_json::to_writer(buf_writer, &play_board.players)
-                    .map_err(|err| err.to_string())
+        result = siv
+            .call_on_name("playboard", move |play_board: &mut PlayBoard| {
+                file.and_then(|f| {
+                    series::write_save(
+                        BufWriter::new(f),
+                        &play_board.players,
+                        play_board.series.as_ref(),
+                    )
+                })?;
+                play_board.mark_saved(Path::new(&*filename));
+                Ok(())
             })
-        });
+            .expect("`playboard` must exists");
+    }
 
-        match result.unwrap() {
-            Ok(()) => {}
-            Err(err) => {
-                siv.add_layer(
-                    OnEventView::new(Dialog::new().title("ERROR").content(TextView::new(err)))
-                        .on_event('q', |s| {
-                            s.pop_layer();
-                        })
-                        .on_event(Key::Enter, |s| {
-                            s.pop_layer();
-                        }),
-                );
-                siv.pop_layer();
-            }
-        }
+    siv.pop_layer();
+    if let Err(err) = result {
+        show_error(siv, err);
+    }
+}
+
+fn export_data(siv: &mut Cursive) {
+    let filename = siv.call_on_name("export_filename", |view: &mut EditView| view.get_content());
+
+    let mut result = Ok(());
+    if let Some(filename) = filename {
+        let csv = siv
+            .call_on_name("playboard", |play_board: &mut PlayBoard| {
+                snapshot::to_csv(&play_board.players)
+            })
+            .expect("`playboard` must exists");
+        result = fs::write(&*filename, csv).map_err(|err| err.to_string());
     }
 
     siv.pop_layer();
+    if let Err(err) = result {
+        show_error(siv, err);
+    }
 }
 
 fn load_data(siv: &mut Cursive) {
     let filepath = siv.call_on_name("load_data_path", |view: &mut EditView| view.get_content());
+    let should_watch = siv
+        .call_on_name("load_data_watch", |view: &mut Checkbox| view.is_checked())
+        .unwrap_or(false);
+    let cb_sink = siv.cb_sink().clone();
 
+    let mut result = Ok(());
     if let Some(filepath) = filepath {
-        let file = fs::File::open(&*filepath).map_err(|err| err.to_string());
-        let result = siv.call_on_name("playboard", move |play_board: &mut PlayBoard| {
-            file.and_then(|f| {
-                let buf_reader = BufReader::new(f);
-                let players = match serde_json::from_reader::<_, Players>(buf_reader) {
-                    Ok(players) => players,
-                    Err(err) => return Err(err.to_string()),
-                };
+        let path = Path::new(&*filepath);
+        let save = read_save(path);
+        result = siv
+            .call_on_name("playboard", move |play_board: &mut PlayBoard| {
+                save.map(|(players, series)| {
+                    play_board.set_players(players);
+                    play_board.series = series;
+                    play_board.broadcast_players();
+                    play_board.unsaved_changes = false;
+                    if should_watch {
+                        play_board.watch_file(path, cb_sink);
+                    } else {
+                        play_board.watched = None;
+                    }
+                })
+            })
+            .expect("`playboard` must exists");
+    }
 
-                play_board.players = players;
+    siv.pop_layer();
+    match result {
+        Ok(()) => schedule_bot_turn(siv),
+        Err(err) => show_error(siv, err),
+    }
+}
 
-                Ok(())
+/// The game in a save file, leaving out the match it may belong to.
+fn read_players(path: &Path) -> Result<Players, String> {
+    read_save(path).map(|(players, _)| players)
+}
+
+/// The game in a file that just changed on disk. The file may be caught halfway
+/// through a write, which gives `None`: the next change brings the rest.
+fn read_changed_file(path: &Path) -> Option<Players> {
+    read_players(path).ok()
+}
+
+fn read_save(path: &Path) -> Result<(Players, Option<Series>), String> {
+    let file = fs::File::open(path).map_err(|err| err.to_string())?;
+    series::read_save(BufReader::new(file))
+}
+
+fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{n}{suffix}")
+}
+
+fn show_error(siv: &mut Cursive, err: String) {
+    siv.add_layer(
+        OnEventView::new(Dialog::new().title("ERROR").content(TextView::new(err)))
+            .on_event('q', |s| {
+                s.pop_layer();
             })
-        });
+            .on_event(Key::Enter, |s| {
+                s.pop_layer();
+            }),
+    );
+}
 
-        match result.unwrap() {
-            Ok(()) => {
-                siv.call_on_name("playboard", |play_board: &mut PlayBoard| {
-                    for (name, _) in play_board.players.iter() {
-                        let width = play_board.width.get();
-                        play_board.width.set(width + str_terminal_len(name));
-                    }
-                });
-            }
-            Err(err) => {
-                siv.add_layer(
-                    OnEventView::new(Dialog::new().title("ERROR").content(TextView::new(err)))
-                        .on_event('q', |s| {
-                            s.pop_layer();
-                        })
-                        .on_event(Key::Enter, |s| {
-                            s.pop_layer();
-                        }),
-                );
-                siv.pop_layer();
-            }
-        }
+/// Centers `label` in the label column, or aligns it left in the compact layout.
+#[inline]
+fn label_indent(label: &str, label_width: usize, is_compact: bool) -> usize {
+    if is_compact {
+        2
+    } else {
+        label_width.saturating_sub(label.width()) / 2
     }
-
-    siv.pop_layer();
 }
 
 #[inline]
@@ -0,0 +1,245 @@
+use std::io;
+
+use cursive::{CbSink, Cursive};
+
+use super::{finish_turn, schedule_bot_turn, show_error, show_final_results, PlayBoard};
+use crate::net::{Client, ClientId, ClientMessage, Host, ServerMessage};
+use crate::players::ScoreInput;
+
+pub(super) enum Network {
+    Host(Host),
+    Client(Client),
+}
+
+impl PlayBoard {
+    /// Hosts this game on `port`. The host may also sit at the table as `name`.
+    pub fn host_game(&mut self, port: u16, name: Option<&str>, cb_sink: CbSink) -> io::Result<()> {
+        if let Some(name) = name {
+            self.players
+                .add_player(name)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+        }
+        let host = Host::start(port, cb_sink, on_client_message, on_client_disconnect)?;
+        self.network = Some(Network::Host(host));
+        Ok(())
+    }
+
+    pub fn join_game(&mut self, addr: &str, name: &str, cb_sink: CbSink) -> io::Result<()> {
+        let client = Client::connect(
+            addr,
+            Some(name.trim()),
+            cb_sink,
+            on_server_message,
+            on_server_disconnect,
+        )?;
+        self.network = Some(Network::Client(client));
+        Ok(())
+    }
+
+    pub(super) fn watch_game(&mut self, addr: &str, cb_sink: CbSink) -> io::Result<()> {
+        let client = Client::connect(addr, None, cb_sink, on_server_message, on_server_disconnect)?;
+        self.network = Some(Network::Client(client));
+        Ok(())
+    }
+
+    #[inline]
+    pub(super) fn is_client(&self) -> bool {
+        matches!(self.network, Some(Network::Client(_)))
+    }
+
+    /// Everything but scoring belongs to the host.
+    pub(super) fn may_edit(&self) -> Result<(), String> {
+        if self.is_client() {
+            Err("Only the host can change the game".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Records a score, or asks the host to when this board is a client.
+    /// Returns whether the game was already over, if the score was applied here.
+    pub(super) fn record_score(
+        &mut self,
+        name: &str,
+        score: ScoreInput,
+    ) -> Result<Option<bool>, String> {
+        self.may_change_card()?;
+        self.players.check_score(name, score)?;
+        if let Some(Network::Client(client)) = &self.network {
+            let Some(own_name) = client.name() else {
+                return Err("Spectators cannot score".to_string());
+            };
+            if name != own_name {
+                return Err(format!("You can only score for `{own_name}`"));
+            }
+            if self.players.current_player().map(String::as_str) != Some(name) {
+                return Err("It is not your turn".to_string());
+            }
+            client
+                .send(&ClientMessage::Score { input: score })
+                .map_err(|err| err.to_string())?;
+            return Ok(None);
+        }
+
+        let was_over = self.players.is_game_over();
+        self.players.calculate_score(name, score);
+        self.players_changed();
+        Ok(Some(was_over))
+    }
+
+    /// Lets every client know about a change made on the host.
+    pub(super) fn broadcast_players(&self) {
+        if let Some(Network::Host(host)) = &self.network {
+            host.broadcast(&ServerMessage::State(self.players.clone()));
+        }
+    }
+
+    /// Whether `name` plays from another machine connected to this host.
+    pub(super) fn is_remote_player(&self, name: &str) -> bool {
+        self.host().is_some_and(|host| host.is_connected(name))
+    }
+
+    fn host(&self) -> Option<&Host> {
+        match &self.network {
+            Some(Network::Host(host)) => Some(host),
+            _ => None,
+        }
+    }
+}
+
+fn on_client_message(siv: &mut Cursive, id: ClientId, message: ClientMessage) {
+    match message {
+        ClientMessage::Join { name } => {
+            let name = name.trim().to_string();
+            siv.call_on_name("playboard", |play_board: &mut PlayBoard| {
+                let host = play_board.host()?.clone();
+                // Someone rejoining keeps their scores, but no one takes over a seat
+                // played on the host
+                let added = if host.is_connected(&name) {
+                    Err(format!("`{name}` is already at the table"))
+                } else if play_board.players.bot_difficulty(&name).is_some() {
+                    Err(format!("`{name}` is a computer player"))
+                } else if play_board.players.get_player_score(&name).is_some() {
+                    if host.has_joined(&name) {
+                        Ok(())
+                    } else {
+                        Err(format!("`{name}` plays on the host"))
+                    }
+                } else {
+                    play_board.players.add_player(&name)
+                };
+                if let Err(err) = added {
+                    host.send(id, &ServerMessage::Rejected(err));
+                    host.disconnect(id);
+                    return None;
+                }
+                host.set_name(id, &name);
+                host.send(id, &ServerMessage::Welcome { name });
+                play_board.players_changed();
+                Some(())
+            });
+            schedule_bot_turn(siv);
+        }
+        ClientMessage::Watch => {
+            siv.call_on_name("playboard", |play_board: &mut PlayBoard| {
+                if let Some(host) = play_board.host() {
+                    host.send(id, &ServerMessage::State(play_board.players.clone()));
+                }
+            });
+        }
+        ClientMessage::Score { input } => {
+            let result = siv
+                .call_on_name("playboard", |play_board: &mut PlayBoard| {
+                    let host = play_board.host()?.clone();
+                    let name = host.client_name(id)?;
+                    if play_board.players.current_player() != Some(&name) {
+                        host.send(
+                            id,
+                            &ServerMessage::Rejected("It is not your turn".to_string()),
+                        );
+                        return None;
+                    }
+                    match play_board.record_score(&name, input) {
+                        Ok(was_over) => was_over,
+                        Err(err) => {
+                            host.send(id, &ServerMessage::Rejected(err));
+                            None
+                        }
+                    }
+                })
+                .flatten();
+
+            if let Some(was_over) = result {
+                finish_turn(siv, was_over);
+            }
+        }
+    }
+}
+
+fn on_client_disconnect(siv: &mut Cursive, id: ClientId) {
+    siv.call_on_name("playboard", |play_board: &mut PlayBoard| {
+        if let Some(host) = play_board.host() {
+            host.disconnect(id);
+        }
+    });
+}
+
+fn on_server_message(siv: &mut Cursive, message: ServerMessage) {
+    match message {
+        ServerMessage::Welcome { .. } => {}
+        ServerMessage::State(players) => {
+            let is_finished = siv
+                .call_on_name("playboard", |play_board: &mut PlayBoard| {
+                    let was_over = play_board.players.is_game_over();
+                    play_board.set_players(players);
+                    // A spectator's screen takes no input, so no dialog to dismiss
+                    play_board.spectator.is_none() && !was_over && play_board.players.is_game_over()
+                })
+                .unwrap_or(false);
+
+            if is_finished {
+                show_final_results(siv);
+            }
+        }
+        ServerMessage::Rejected(reason) => show_error(siv, reason),
+    }
+}
+
+fn on_server_disconnect(siv: &mut Cursive) {
+    show_error(siv, "The connection to the host was lost".to_string());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::players::Category;
+
+    #[test]
+    fn host_rejects_filled_and_impossible_scores() {
+        let mut play_board = PlayBoard::new();
+        play_board.players.add_player("A").unwrap();
+
+        assert!(play_board
+            .record_score("A", ScoreInput::Choice(Some(20)))
+            .is_ok());
+        assert!(play_board
+            .record_score("A", ScoreInput::Choice(Some(25)))
+            .is_err());
+        assert!(play_board
+            .record_score("A", ScoreInput::Ones(Some(65535)))
+            .is_err());
+        assert!(play_board
+            .record_score("A", ScoreInput::Twos(Some(3)))
+            .is_err());
+        assert!(play_board
+            .record_score("A", ScoreInput::Ones(None))
+            .is_err());
+
+        let scoreboard = play_board
+            .players
+            .get_player_score(&"A".to_string())
+            .unwrap();
+        assert_eq!(scoreboard.filled_count(), 1);
+        assert_eq!(Category::Choice.value(scoreboard), Some(20));
+    }
+}
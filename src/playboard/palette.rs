@@ -0,0 +1,167 @@
+use cursive::{
+    event::Key,
+    traits::{Nameable, Resizable, Scrollable},
+    views::{Dialog, EditView, LinearLayout, OnEventView, SelectView},
+    Cursive,
+};
+
+use super::PlayBoard;
+use crate::keymap::{Action, Keymap};
+
+const PALETTE_HEIGHT: usize = 12;
+
+/// Lists every action, filtered as the name is typed. Picking one runs it
+/// as if its keys were pressed.
+pub(super) fn show_palette(siv: &mut Cursive, keymap_items: Vec<(String, Action)>) {
+    let mut select = SelectView::new();
+    select.add_all(keymap_items.iter().cloned());
+    select.set_on_submit(|s, action: &Action| run(s, *action));
+
+    siv.add_layer(
+        OnEventView::new(
+            Dialog::around(
+                LinearLayout::vertical()
+                    .child(
+                        EditView::new()
+                            .on_edit(move |s, query, _| {
+                                let items = filter(&keymap_items, query);
+                                s.call_on_name(
+                                    "palette_actions",
+                                    |view: &mut SelectView<Action>| {
+                                        view.clear();
+                                        view.add_all(items);
+                                    },
+                                );
+                            })
+                            .on_submit(|s, _| {
+                                let action = s
+                                    .call_on_name(
+                                        "palette_actions",
+                                        |view: &mut SelectView<Action>| view.selection(),
+                                    )
+                                    .flatten();
+                                if let Some(action) = action {
+                                    run(s, *action);
+                                }
+                            }),
+                    )
+                    .child(
+                        select
+                            .with_name("palette_actions")
+                            .scrollable()
+                            .fixed_height(PALETTE_HEIGHT),
+                    ),
+            )
+            .title("Command Palette"),
+        )
+        .on_event(Key::Esc, |s| {
+            s.pop_layer();
+        }),
+    );
+}
+
+/// Every action but the palette itself, labeled with its keys.
+pub(super) fn palette_items(keymap: &Keymap) -> Vec<(String, Action)> {
+    Action::ALL
+        .into_iter()
+        .filter(|action| *action != Action::CommandPalette)
+        .map(|action| {
+            let label = format!("{:<36}{}", action.description(), keymap.keys_of(action));
+            (label, action)
+        })
+        .collect()
+}
+
+fn run(siv: &mut Cursive, action: Action) {
+    siv.pop_layer();
+    if let Some(result) = siv.call_on_name("playboard", |play_board: &mut PlayBoard| {
+        play_board.run_action(action)
+    }) {
+        result.process(siv);
+    }
+}
+
+/// The items matching `query`, best match first.
+fn filter(items: &[(String, Action)], query: &str) -> Vec<(String, Action)> {
+    let mut matches = items
+        .iter()
+        .filter_map(|(label, action)| {
+            let score = fuzzy_score(query, action.description())?;
+            Some((score, label.clone(), *action))
+        })
+        .collect::<Vec<_>>();
+    // Stable, so equal scores keep the order of `Action::ALL`
+    matches.sort_by_key(|(score, ..)| std::cmp::Reverse(*score));
+    matches
+        .into_iter()
+        .map(|(_, label, action)| (label, action))
+        .collect()
+}
+
+/// Whether every character of `query` appears in `text` in order, and how well:
+/// runs of characters and the starts of words count more.
+fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let text = text.to_lowercase().chars().collect::<Vec<_>>();
+    let mut position = 0;
+    let mut last_match = None;
+    let mut score = 0;
+
+    for query_char in query
+        .to_lowercase()
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+    {
+        let index = position + text[position..].iter().position(|ch| *ch == query_char)?;
+        score += 1;
+        if index == 0 || text[index - 1] == ' ' {
+            score += 2;
+        }
+        if last_match.is_some_and(|last| last + 1 == index) {
+            score += 2;
+        }
+        last_match = Some(index);
+        position = index + 1;
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letters_must_appear_in_order() {
+        assert!(fuzzy_score("lstr", "add score at large straight").is_some());
+        assert!(fuzzy_score("LSTR", "add score at large straight").is_some());
+        assert!(fuzzy_score("rtsl", "add score at large straight").is_none());
+        assert!(fuzzy_score("xyz", "add score at large straight").is_none());
+        assert_eq!(fuzzy_score("", "quit this program"), Some(0));
+    }
+
+    #[test]
+    fn runs_and_word_starts_score_more() {
+        // Every letter starts a word or follows the last one
+        assert_eq!(
+            fuzzy_score("und", "undo the last score"),
+            Some(3 + 2 + 2 + 2)
+        );
+        // Both start a word
+        assert_eq!(fuzzy_score("qp", "quit this program"), Some(3 + 3));
+        // Only `q` does, and `i` does not follow it
+        assert_eq!(fuzzy_score("qi", "quit this program"), Some(3 + 1));
+        assert!(fuzzy_score("sav", "save the data") > fuzzy_score("sav", "scratch yacht value"));
+    }
+
+    #[test]
+    fn best_matches_come_first() {
+        let items = palette_items(&Keymap::default());
+        let matches = filter(&items, "yacht");
+        let actions = matches
+            .iter()
+            .map(|(_, action)| *action)
+            .collect::<Vec<_>>();
+        assert_eq!(actions[..2], [Action::Yacht, Action::YachtMiss]);
+        assert!(!actions.contains(&Action::Quit));
+        assert!(filter(&items, "zzz").is_empty());
+    }
+}
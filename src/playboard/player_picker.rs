@@ -0,0 +1,225 @@
+use std::rc::Rc;
+
+use cursive::{
+    event::{Event, EventResult, EventTrigger, Key},
+    traits::Nameable,
+    view::Finder,
+    views::{Dialog, EditView, LinearLayout, OnEventView, SelectView, TextView},
+    Cursive,
+};
+
+use super::{show_error, PlayBoard};
+
+type OnPick = Rc<dyn Fn(&mut Cursive, &str)>;
+
+/// Asks which player `title` is for: pick from the list, type a number, or
+/// type the name, where Tab completes it.
+pub(super) fn choose_player<F>(siv: &mut Cursive, title: &str, on_pick: F)
+where
+    F: Fn(&mut Cursive, &str) + 'static,
+{
+    let Some((names, current)) = siv.call_on_name("playboard", |play_board: &mut PlayBoard| {
+        let names = play_board
+            .players
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        (names, play_board.players.current_player().cloned())
+    }) else {
+        return;
+    };
+    if names.is_empty() {
+        show_error(siv, "There are no players yet".to_string());
+        return;
+    }
+
+    let names = Rc::new(names);
+    let on_pick: OnPick = Rc::new(on_pick);
+
+    let mut select = SelectView::new();
+    fill(&mut select, &names, "");
+    if let Some(current) =
+        current.and_then(|current| names.iter().position(|name| *name == current))
+    {
+        select.set_selection(current);
+    }
+    let on_submit = Rc::clone(&on_pick);
+    select.set_on_submit(move |s, name: &String| pick(s, &on_submit, name));
+
+    let edit = {
+        let names = Rc::clone(&names);
+        let on_pick = Rc::clone(&on_pick);
+        let names_for_edit = Rc::clone(&names);
+        EditView::new()
+            .on_edit(move |s, text, _| {
+                s.call_on_name("player_pick_list", |view: &mut SelectView<String>| {
+                    fill(view, &names_for_edit, text);
+                });
+            })
+            .on_submit(move |s, text| {
+                let name = if text.trim().is_empty() {
+                    s.call_on_name("player_pick_list", |view: &mut SelectView<String>| {
+                        view.selection()
+                    })
+                    .flatten()
+                    .map(|name| Ok(name.to_string()))
+                    .unwrap_or_else(|| Err("Choose a player".to_string()))
+                } else {
+                    resolve(&names, text)
+                };
+                match name {
+                    Ok(name) => pick(s, &on_pick, &name),
+                    Err(err) => show_error(s, err),
+                }
+            })
+            .with_name("player_pick_name")
+    };
+
+    let digit_names = Rc::clone(&names);
+    let complete_names = Rc::clone(&names);
+    siv.add_layer(
+        OnEventView::new(
+            Dialog::new().title(title).content(
+                LinearLayout::vertical()
+                    .child(TextView::new(
+                        "Choose the player, or type a name (Tab completes it)",
+                    ))
+                    .child(edit)
+                    .child(select.with_name("player_pick_list")),
+            ),
+        )
+        // A number picks a player, unless a name is being typed
+        .on_pre_event_inner(
+            EventTrigger::from_fn(|event| matches!(event, Event::Char('1'..='9'))),
+            move |view, event| {
+                let Event::Char(digit) = event else {
+                    return None;
+                };
+                let is_typing = view
+                    .call_on_name("player_pick_name", |edit: &mut EditView| {
+                        !edit.get_content().is_empty()
+                    })
+                    .unwrap_or(false);
+                let index = *digit as usize - '1' as usize;
+                if is_typing {
+                    return None;
+                }
+                let name = digit_names.get(index)?.clone();
+                let on_pick = Rc::clone(&on_pick);
+                Some(EventResult::with_cb(move |s| pick(s, &on_pick, &name)))
+            },
+        )
+        .on_pre_event_inner(Key::Tab, move |view, _| {
+            view.call_on_name("player_pick_name", |edit: &mut EditView| {
+                let completed = complete(&complete_names, &edit.get_content());
+                completed.map(|completed| edit.set_content(completed))
+            })
+            .flatten()
+            .map_or(Some(EventResult::Consumed(None)), |callback| {
+                Some(EventResult::Consumed(Some(callback)))
+            })
+        })
+        .on_event(Key::Esc, |s| {
+            s.pop_layer();
+        }),
+    );
+}
+
+fn pick(siv: &mut Cursive, on_pick: &OnPick, name: &str) {
+    siv.pop_layer();
+    on_pick(siv, name);
+}
+
+/// Lists the players whose name starts with `prefix`, numbered by seat.
+fn fill(select: &mut SelectView<String>, names: &[String], prefix: &str) {
+    let prefix = prefix.trim();
+    select.clear();
+    for (i, name) in names.iter().enumerate() {
+        if starts_with_ignore_case(name, prefix) {
+            select.add_item(format!("{}  {name}", i + 1), name.clone());
+        }
+    }
+}
+
+/// The player `text` names: an exact name, or the only one it starts.
+/// Spaces around `text` are left out, as they are in names.
+fn resolve(names: &[String], text: &str) -> Result<String, String> {
+    let text = text.trim();
+    if let Some(name) = names.iter().find(|name| name.as_str() == text) {
+        return Ok(name.clone());
+    }
+
+    let candidates = names
+        .iter()
+        .filter(|name| starts_with_ignore_case(name, text))
+        .collect::<Vec<_>>();
+    match candidates.as_slice() {
+        [] => Err(format!("There is no player named `{text}`")),
+        [name] => Ok(name.to_string()),
+        _ => Err(format!(
+            "`{text}` could be any of {}",
+            candidates
+                .iter()
+                .map(|name| format!("`{name}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+/// The longest name prefix that every player starting with `text` shares,
+/// whatever the case, spelled as the first of them.
+fn complete(names: &[String], text: &str) -> Option<String> {
+    let text = text.trim();
+    let mut candidates = names
+        .iter()
+        .filter(|name| starts_with_ignore_case(name, text));
+    let first = candidates.next()?;
+    let common = candidates.fold(first.as_str(), |common, name| {
+        let length = common
+            .char_indices()
+            .zip(name.chars())
+            .take_while(|((_, lhs), rhs)| lhs.to_lowercase().eq(rhs.to_lowercase()))
+            .last()
+            .map_or(0, |((index, ch), _)| index + ch.len_utf8());
+        &common[..length]
+    });
+    (common.chars().count() > text.chars().count()).then(|| common.to_string())
+}
+
+#[inline]
+fn starts_with_ignore_case(name: &str, prefix: &str) -> bool {
+    name.to_lowercase().starts_with(&prefix.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn resolves_exact_names_then_prefixes() {
+        let names = names(&["Al", "Alice", "Bob"]);
+        assert_eq!(resolve(&names, "Al").unwrap(), "Al");
+        assert_eq!(resolve(&names, "ali").unwrap(), "Alice");
+        assert_eq!(resolve(&names, "alice ").unwrap(), "Alice");
+        assert_eq!(resolve(&names, " b").unwrap(), "Bob");
+        assert!(resolve(&names, "a")
+            .unwrap_err()
+            .contains("could be any of"));
+        assert!(resolve(&names, "carol").is_err());
+    }
+
+    #[test]
+    fn completes_the_shared_prefix_whatever_the_case() {
+        let names = names(&["Alice", "ALINA", "Bob"]);
+        assert_eq!(complete(&names, "a").as_deref(), Some("Ali"));
+        assert_eq!(complete(&names, "b").as_deref(), Some("Bob"));
+        // Nothing longer to offer
+        assert_eq!(complete(&names, "ali"), None);
+        assert_eq!(complete(&names, "z"), None);
+    }
+}
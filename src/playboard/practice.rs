@@ -0,0 +1,247 @@
+use cursive::{
+    event::Key,
+    views::{Dialog, LinearLayout, OnEventView, SelectView, TextView},
+    Cursive,
+};
+
+use super::{finish_turn, show_error, PlayBoard};
+use crate::advisor::MAX_REROLLS;
+use crate::bot::Rng;
+use crate::highscore::{format_scorecard, HighScoreEntry, HighScores};
+use crate::players::{Category, Dice, RULE_SET};
+
+pub(super) const PRACTICE_PLAYER: &str = "Solo";
+
+#[derive(Default)]
+pub(super) struct DiceTray {
+    pub(super) dice: Option<Dice>,
+    pub(super) hold: [bool; 5],
+    pub(super) rerolls_left: u8,
+}
+
+impl PlayBoard {
+    /// A practice game may go into the high scores, so nothing but the dice tray
+    /// changes its card.
+    pub(super) fn may_change_card(&self) -> Result<(), String> {
+        if self.practice.is_some() {
+            Err("A practice game is only played with the dice tray".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl DiceTray {
+    /// Rolls every die for a new turn, or the dice not held while rerolls are left.
+    pub(super) fn roll(&mut self, rng: &mut Rng) {
+        match self.dice.as_mut() {
+            None => {
+                let mut dice = [1; 5];
+                rng.roll(&mut dice, [false; 5]);
+                self.dice = Some(dice);
+                self.hold = [false; 5];
+                self.rerolls_left = MAX_REROLLS;
+            }
+            Some(dice) if self.rerolls_left > 0 => {
+                rng.roll(dice, self.hold);
+                self.rerolls_left -= 1;
+            }
+            Some(_) => {}
+        }
+    }
+
+    #[inline]
+    pub(super) fn toggle_hold(&mut self, index: usize) {
+        if self.dice.is_some() {
+            self.hold[index] = !self.hold[index];
+        }
+    }
+
+    pub(super) fn describe(&self, roll_key: &str) -> String {
+        let Some(dice) = self.dice else {
+            return format!("Press `{roll_key}` to roll the dice");
+        };
+
+        let dice = dice
+            .iter()
+            .zip(self.hold)
+            .map(|(die, held)| {
+                if held {
+                    format!("[{die}]")
+                } else {
+                    format!(" {die} ")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        format!("Dice: {dice}    Rerolls left: {}", self.rerolls_left)
+    }
+}
+
+pub(super) fn choose_category(siv: &mut Cursive) {
+    let choices = siv
+        .call_on_name("playboard", |play_board: &mut PlayBoard| {
+            let dice = play_board.practice.as_ref()?.dice?;
+            let scoreboard = play_board
+                .players
+                .get_player_score(&PRACTICE_PLAYER.to_string())?;
+
+            let choices = Category::ALL
+                .into_iter()
+                .filter(|category| category.is_open(scoreboard))
+                .map(|category| {
+                    let label = format!("{:<16}{:>3}", category.name(), category.score(&dice));
+                    (label, category)
+                })
+                .collect::<Vec<_>>();
+            Some((dice, choices))
+        })
+        .flatten();
+
+    let Some((dice, choices)) = choices else {
+        return;
+    };
+
+    let mut select = SelectView::new();
+    select.add_all(choices);
+    select.set_on_submit(move |s, category: &Category| {
+        let was_over = s
+            .call_on_name("playboard", |play_board: &mut PlayBoard| {
+                let was_over = play_board.players.is_game_over();
+                play_board
+                    .players
+                    .calculate_score(PRACTICE_PLAYER, category.to_score_input(&dice));
+                play_board.players_changed();
+                play_board.practice = Some(DiceTray::default());
+                was_over
+            })
+            .unwrap_or(false);
+
+        s.pop_layer();
+        finish_turn(s, was_over);
+    });
+
+    siv.add_layer(
+        OnEventView::new(Dialog::new().title("Score").content(select)).on_event(Key::Esc, |s| {
+            s.pop_layer();
+        }),
+    );
+}
+
+/// Records the finished practice game in the high-score table.
+pub(super) fn finish_game(siv: &mut Cursive) {
+    let Some(scoreboard) = siv
+        .call_on_name("playboard", |play_board: &mut PlayBoard| {
+            play_board
+                .players
+                .get_player_score(&PRACTICE_PLAYER.to_string())
+                .copied()
+        })
+        .flatten()
+    else {
+        return;
+    };
+
+    let mut message = format!("Your total is {}.", scoreboard.total_score);
+    let recorded = HighScores::load().and_then(|mut high_scores| {
+        let place = high_scores.record(RULE_SET, scoreboard);
+        high_scores.save().map(|()| place)
+    });
+    match recorded {
+        Ok(Some(place)) => message.push_str(&format!("\nNew high score, #{}!", place + 1)),
+        Ok(None) => {}
+        Err(err) => message.push_str(&format!("\nCannot record the high score: {err}")),
+    }
+
+    siv.add_layer(
+        Dialog::new()
+            .title("Practice Finished")
+            .content(TextView::new(message))
+            .button("High Scores", |s| {
+                s.pop_layer();
+                show_high_scores(s);
+            })
+            .button("Play Again", |s| {
+                s.call_on_name("playboard", PlayBoard::start_practice);
+                s.pop_layer();
+            })
+            .button("Close", |s| {
+                s.pop_layer();
+            }),
+    );
+}
+
+pub(super) fn show_high_scores(siv: &mut Cursive) {
+    let high_scores = match HighScores::load() {
+        Ok(high_scores) => high_scores,
+        Err(err) => {
+            show_error(siv, err);
+            return;
+        }
+    };
+
+    let mut tables = LinearLayout::vertical();
+    for (rule_set, entries) in high_scores.tables() {
+        let mut select = SelectView::new();
+        for (place, entry) in entries.iter().enumerate() {
+            let label = format!(
+                "#{:<3}{}  {:>3}",
+                place + 1,
+                entry.date,
+                entry.scoreboard.total_score
+            );
+            select.add_item(label, entry.clone());
+        }
+        select.set_on_submit(|s, entry: &HighScoreEntry| {
+            s.add_layer(
+                Dialog::info(format_scorecard(&entry.scoreboard)).title(entry.date.clone()),
+            );
+        });
+
+        tables.add_child(TextView::new(format!("<{rule_set}>")));
+        tables.add_child(select);
+    }
+    if tables.is_empty() {
+        tables.add_child(TextView::new("No practice games finished yet"));
+    }
+
+    siv.add_layer(
+        OnEventView::new(
+            Dialog::new()
+                .title("High Scores")
+                .content(tables)
+                .button("Close", |s| {
+                    s.pop_layer();
+                }),
+        )
+        .on_event(Key::Esc, |s| {
+            s.pop_layer();
+        }),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::players::ScoreInput;
+
+    #[test]
+    fn practice_card_is_only_scored_from_the_tray() {
+        let mut play_board = PlayBoard::new();
+        play_board.start_practice();
+        assert!(play_board
+            .record_score(PRACTICE_PLAYER, ScoreInput::Yacht(true))
+            .is_err());
+
+        let dice = [6; 5];
+        play_board
+            .players
+            .calculate_score(PRACTICE_PLAYER, Category::Yacht.to_score_input(&dice));
+        assert!(play_board.undo().is_err());
+        let scoreboard = play_board
+            .players
+            .get_player_score(&PRACTICE_PLAYER.to_string())
+            .unwrap();
+        assert_eq!(scoreboard.total_score, 50);
+    }
+}
@@ -0,0 +1,218 @@
+use super::glyphs::Glyphs;
+use super::ordinal;
+use crate::keymap::{Action, Keymap};
+use crate::players::{BonusStatus, Category, Players, Scoreboard, TeamMode};
+
+/// What a row shows for one player, and how it is marked
+pub(super) type RowValue = fn(&Players, &str, &Scoreboard, &Glyphs) -> (String, Shade);
+
+/// Which of the `BoardStyles` a cell is drawn with
+#[derive(Clone, Copy)]
+pub(super) enum Shade {
+    Plain,
+    Empty,
+    Scratched,
+    Bonus,
+    OutOfReach,
+}
+
+pub(super) enum Row {
+    Separator,
+    Category(Category),
+    Computed {
+        label: &'static str,
+        short_label: &'static str,
+        value: RowValue,
+    },
+}
+
+impl Row {
+    /// The label, and the keys that score the row if there are any.
+    pub(super) fn label(
+        &self,
+        compact: bool,
+        keymap: &Keymap,
+    ) -> Option<(&'static str, Option<String>)> {
+        let keys_of = |category: Category| {
+            let keys = keymap.keys_of(score_action(category));
+            (!keys.is_empty()).then_some(keys)
+        };
+        match self {
+            Self::Separator => None,
+            Self::Category(category) if compact => {
+                Some((short_name(*category), keys_of(*category)))
+            }
+            Self::Category(category) => Some((category.name(), keys_of(*category))),
+            Self::Computed { short_label, .. } if compact => Some((short_label, None)),
+            Self::Computed { label, .. } => Some((label, None)),
+        }
+    }
+
+    pub(super) fn value(
+        &self,
+        players: &Players,
+        name: &str,
+        scoreboard: &Scoreboard,
+        glyphs: &Glyphs,
+    ) -> Option<(String, Shade)> {
+        match self {
+            Self::Separator => None,
+            Self::Category(category) => Some(match category.value(scoreboard) {
+                None => (glyphs.empty.to_string(), Shade::Empty),
+                Some(0) => ("0".to_string(), Shade::Scratched),
+                Some(num) => (num.to_string(), Shade::Plain),
+            }),
+            Self::Computed { value, .. } => Some(value(players, name, scoreboard, glyphs)),
+        }
+    }
+}
+
+/// The rows under the player names: the upper section, its bonus, the lower
+/// section, the totals and, when some play in teams, the teams. Categories are
+/// laid out in `Category::ALL` order.
+pub(super) fn board_rows(with_teams: bool) -> Vec<Row> {
+    let (upper, lower): (Vec<_>, Vec<_>) = Category::ALL
+        .into_iter()
+        .partition(|category| category.face().is_some());
+
+    let mut rows = upper.into_iter().map(Row::Category).collect::<Vec<_>>();
+    rows.push(Row::Separator);
+    rows.push(Row::Computed {
+        label: "Left to get bonus",
+        short_label: "To bonus",
+        value: bonus_left,
+    });
+    rows.push(Row::Computed {
+        label: "Par (3 each)",
+        short_label: "Par",
+        value: |_, _, scoreboard, _| (format!("{:+}", scoreboard.par_difference()), Shade::Plain),
+    });
+    rows.push(Row::Computed {
+        label: "Bonus",
+        short_label: "Bonus",
+        value: |_, _, scoreboard, _| {
+            let shade = if scoreboard.bonus > 0 {
+                Shade::Bonus
+            } else {
+                Shade::Plain
+            };
+            (scoreboard.bonus.to_string(), shade)
+        },
+    });
+    rows.push(Row::Separator);
+    rows.extend(lower.into_iter().map(Row::Category));
+    rows.push(Row::Separator);
+    rows.push(Row::Separator);
+    rows.push(Row::Computed {
+        label: "Total",
+        short_label: "Total",
+        value: |_, _, scoreboard, _| (scoreboard.total_score.to_string(), Shade::Plain),
+    });
+    rows.push(Row::Computed {
+        label: "Rank",
+        short_label: "Rank",
+        value: rank,
+    });
+    rows.push(Row::Computed {
+        label: "Max possible",
+        short_label: "Max",
+        value: max_possible,
+    });
+    if with_teams {
+        rows.push(Row::Separator);
+        rows.push(Row::Computed {
+            label: "Team / next to roll",
+            short_label: "Team",
+            value: |players, name, _, _| (team_label(players, name), Shade::Plain),
+        });
+        rows.push(Row::Computed {
+            label: "Team total",
+            short_label: "Team tot",
+            value: |players, name, _, _| {
+                let total = players.team_of(name).map(|team| players.team_total(team));
+                (
+                    total.map_or_else(String::new, |total| total.to_string()),
+                    Shade::Plain,
+                )
+            },
+        });
+    }
+    rows
+}
+
+/// The team a player is in, or who rolls next on a shared card.
+pub(super) fn team_label(players: &Players, name: &str) -> String {
+    match players.team_of(name) {
+        Some(team) if team.mode == TeamMode::Shared => {
+            players.next_member(team).unwrap_or_default().to_string()
+        }
+        Some(team) => team.name.clone(),
+        None => String::new(),
+    }
+}
+
+fn bonus_left(_: &Players, _: &str, scoreboard: &Scoreboard, glyphs: &Glyphs) -> (String, Shade) {
+    match scoreboard.bonus_status() {
+        BonusStatus::Secured => (format!("0{}", glyphs.secured), Shade::Bonus),
+        BonusStatus::Possible => (scoreboard.left_to_get_bonus.to_string(), Shade::Plain),
+        BonusStatus::Impossible => (
+            format!("{}{}", scoreboard.left_to_get_bonus, glyphs.out_of_reach),
+            Shade::OutOfReach,
+        ),
+    }
+}
+
+fn rank(players: &Players, name: &str, _: &Scoreboard, _: &Glyphs) -> (String, Shade) {
+    let rank = ordinal(players.rank_of(name).unwrap_or(0));
+    if players.is_tied(name) {
+        (format!("={rank}"), Shade::Plain)
+    } else {
+        (rank, Shade::Plain)
+    }
+}
+
+fn max_possible(
+    players: &Players,
+    name: &str,
+    scoreboard: &Scoreboard,
+    glyphs: &Glyphs,
+) -> (String, Shade) {
+    if players.is_eliminated(name) {
+        (
+            format!("{}{}", scoreboard.max_achievable(), glyphs.out_of_reach),
+            Shade::OutOfReach,
+        )
+    } else {
+        (scoreboard.max_achievable().to_string(), Shade::Plain)
+    }
+}
+
+/// The action that scores `category` on the board
+fn score_action(category: Category) -> Action {
+    match category {
+        Category::Ones => Action::Ones,
+        Category::Twos => Action::Twos,
+        Category::Threes => Action::Threes,
+        Category::Fours => Action::Fours,
+        Category::Fives => Action::Fives,
+        Category::Sixes => Action::Sixes,
+        Category::Choice => Action::Choice,
+        Category::FullHouse => Action::FullHouse,
+        Category::FourOfKind => Action::FourOfKind,
+        Category::SmallStraight => Action::SmallStraight,
+        Category::LargeStraight => Action::LargeStraight,
+        Category::Yacht => Action::Yacht,
+    }
+}
+
+/// Label for the compact layout, at most 7 columns wide
+fn short_name(category: Category) -> &'static str {
+    match category {
+        Category::FullHouse => "F.House",
+        Category::FourOfKind => "4 Kind",
+        Category::SmallStraight => "S.Str.",
+        Category::LargeStraight => "L.Str.",
+        Category::Yacht => "Yacht",
+        _ => category.name(),
+    }
+}
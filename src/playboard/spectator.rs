@@ -0,0 +1,66 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use cursive::{CbSink, Cursive};
+
+use super::{read_changed_file, read_players, PlayBoard};
+use crate::watch::FileWatcher;
+
+/// Extra columns each player gets in the large layout
+pub(super) const LARGE_COLUMN_PADDING: usize = 6;
+
+pub(super) struct Spectator {
+    /// What is being watched, shown at the bottom of the screen
+    pub(super) source: String,
+    file: Option<PathBuf>,
+    _watcher: Option<FileWatcher>,
+}
+
+impl PlayBoard {
+    /// Follows a save file, redrawing whenever it is written.
+    pub fn spectate_file(&mut self, path: &Path, cb_sink: CbSink) -> Result<(), String> {
+        self.set_players(read_players(path)?);
+        self.spectator = Some(Spectator {
+            source: path.display().to_string(),
+            file: Some(path.to_path_buf()),
+            _watcher: Some(FileWatcher::start(
+                path.to_path_buf(),
+                cb_sink,
+                reload_spectated_file,
+            )),
+        });
+        Ok(())
+    }
+
+    /// Follows the game hosted at `addr` without taking a seat.
+    pub fn spectate_host(&mut self, addr: &str, cb_sink: CbSink) -> io::Result<()> {
+        self.watch_game(addr, cb_sink)?;
+        self.spectator = Some(Spectator {
+            source: addr.to_string(),
+            file: None,
+            _watcher: None,
+        });
+        Ok(())
+    }
+
+    /// Wider, centered columns for a big screen.
+    #[inline]
+    pub fn set_large_layout(&mut self, large: bool) {
+        self.large = large;
+    }
+}
+
+fn reload_spectated_file(siv: &mut Cursive) {
+    siv.call_on_name("playboard", |play_board: &mut PlayBoard| {
+        let Some(path) = play_board
+            .spectator
+            .as_ref()
+            .and_then(|spectator| spectator.file.clone())
+        else {
+            return;
+        };
+        if let Some(players) = read_changed_file(&path) {
+            play_board.set_players(players);
+        }
+    });
+}
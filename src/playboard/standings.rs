@@ -0,0 +1,174 @@
+use cursive::{
+    event::Key,
+    traits::Nameable,
+    views::{Dialog, LinearLayout, OnEventView, SelectView, TextView},
+    Cursive,
+};
+use unicode_width::UnicodeWidthStr;
+
+use super::{schedule_bot_turn, show_error, PlayBoard};
+use crate::players::Players;
+use crate::series::{Series, SeriesFormat};
+
+/// The matches offered when starting one.
+const FORMATS: [SeriesFormat; 4] = [
+    SeriesFormat::BestOf(3),
+    SeriesFormat::BestOf(5),
+    SeriesFormat::Total(3),
+    SeriesFormat::Total(5),
+];
+
+pub(super) fn show_new_match(siv: &mut Cursive) {
+    let mut formats = SelectView::new();
+    for format in FORMATS {
+        formats.add_item(format.describe(), format);
+    }
+
+    siv.add_layer(
+        Dialog::new()
+            .title("New Match")
+            .content(
+                LinearLayout::vertical()
+                    .child(TextView::new("Choose the match to play"))
+                    .child(formats.with_name("new_match_format"))
+                    .child(TextView::new("The current scores will be cleared.")),
+            )
+            .button("Ok", start_match)
+            .button("Cancel", |s| {
+                s.pop_layer();
+            }),
+    );
+}
+
+fn start_match(siv: &mut Cursive) {
+    let Some(format) = siv
+        .call_on_name("new_match_format", |view: &mut SelectView<SeriesFormat>| {
+            view.selection()
+        })
+        .flatten()
+    else {
+        return;
+    };
+
+    let result = siv
+        .call_on_name("playboard", |play_board: &mut PlayBoard| {
+            play_board.may_edit()?;
+            play_board.players.clear_score();
+            play_board.series = Some(Series::new(*format));
+            play_board.players_changed();
+            Ok(())
+        })
+        .expect("`playboard` must exists");
+
+    siv.pop_layer();
+    match result {
+        Ok(()) => schedule_bot_turn(siv),
+        Err(err) => show_error(siv, err),
+    }
+}
+
+/// Files away the finished game and starts the next one with the same players.
+pub(super) fn next_game(siv: &mut Cursive) {
+    let result = siv
+        .call_on_name("playboard", |play_board: &mut PlayBoard| {
+            play_board.may_edit()?;
+            let series = play_board
+                .series
+                .as_mut()
+                .ok_or_else(|| "No match is being played".to_string())?;
+            if !play_board.players.is_game_over() {
+                return Err("Finish this game first".to_string());
+            }
+            if series.is_over(&play_board.players) {
+                return Err("The match is already decided".to_string());
+            }
+            play_board.players = series.next_game(&play_board.players);
+            play_board.players_changed();
+            Ok(())
+        })
+        .expect("`playboard` must exists");
+
+    match result {
+        Ok(()) => schedule_bot_turn(siv),
+        Err(err) => show_error(siv, err),
+    }
+}
+
+/// A table of every game in the match, with the totals and wins so far.
+pub(super) fn standings_text(series: &Series, current: &Players) -> String {
+    let standings = series.standings(current);
+    let name_width = standings
+        .iter()
+        .map(|standing| standing.name.width_cjk())
+        .max()
+        .unwrap_or(0)
+        .max("Player".len());
+
+    let game_count = series.finished_games() + 1;
+    let mut text = format!("{}, game {game_count}", series.format.describe());
+    if series.is_over(current) {
+        text.push_str(" (decided)");
+    }
+    text.push_str("\n\n");
+
+    text.push_str(&format!("{:name_width$}", "Player"));
+    for game in 1..=game_count {
+        text.push_str(&format!("  {:>4}", format!("#{game}")));
+    }
+    text.push_str("  Total  Wins\n");
+
+    for standing in &standings {
+        let padding = name_width - standing.name.width_cjk();
+        text.push_str(&format!("{}{:padding$}", standing.name, ""));
+        for total in &standing.totals {
+            match total {
+                Some(total) => text.push_str(&format!("  {total:>4}")),
+                None => text.push_str(&format!("  {:>4}", "-")),
+            }
+        }
+        text.push_str(&format!("  {:>5}  {:>4}\n", standing.total, standing.wins));
+    }
+
+    let leaders = series.leaders(current);
+    let verb = if series.is_over(current) {
+        "won"
+    } else {
+        "leads"
+    };
+    match leaders.as_slice() {
+        [] => {}
+        [leader] => text.push_str(&format!("\n{leader} {verb} the match")),
+        _ => text.push_str(&format!("\nTied: {}", leaders.join(", "))),
+    }
+    text
+}
+
+pub(super) fn show_standings(siv: &mut Cursive) {
+    let Some(text) = siv
+        .call_on_name("playboard", |play_board: &mut PlayBoard| {
+            let series = play_board.series.as_ref()?;
+            Some(standings_text(series, &play_board.players))
+        })
+        .flatten()
+    else {
+        show_error(siv, "No match is being played".to_string());
+        return;
+    };
+
+    siv.add_layer(
+        OnEventView::new(
+            Dialog::new()
+                .title("Match Standings")
+                .content(TextView::new(text))
+                .button("Close", |s| {
+                    s.pop_layer();
+                }),
+        )
+        .on_event('q', |s| {
+            s.pop_layer();
+        })
+        .on_event(Key::Esc, |s| {
+            s.pop_layer();
+        }),
+    );
+}
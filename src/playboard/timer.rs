@@ -0,0 +1,168 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+use std::thread;
+use std::time::Duration;
+
+use cursive::{
+    event::Key,
+    views::{Dialog, OnEventView},
+    CbSink, Cursive,
+};
+
+use super::{finish_turn, show_error, PlayBoard};
+use crate::clock::{Clock, ClockConfig, TimeoutAction};
+use crate::players::Category;
+
+/// How often the clock is checked and redrawn
+const TICK: Duration = Duration::from_millis(250);
+
+/// The clock of a board, and the thread that redraws it while it runs.
+pub(super) struct Timer {
+    clock: Clock,
+    /// Whether the thread should tick. The thread ends once this is dropped.
+    ticking: Arc<AtomicBool>,
+}
+
+impl Timer {
+    fn start(clock: Clock, cb_sink: CbSink) -> Self {
+        let ticking = Arc::new(AtomicBool::new(false));
+        let weak = Arc::downgrade(&ticking);
+        thread::spawn(move || tick_while_alive(&weak, &cb_sink));
+        Self { clock, ticking }
+    }
+}
+
+fn tick_while_alive(ticking: &Weak<AtomicBool>, cb_sink: &CbSink) {
+    loop {
+        thread::sleep(TICK);
+        let Some(ticking) = ticking.upgrade() else {
+            return;
+        };
+        if ticking.load(Ordering::Relaxed) && cb_sink.send(Box::new(tick)).is_err() {
+            return;
+        }
+    }
+}
+
+impl PlayBoard {
+    /// Times the turns played on this board. Clients and spectators keep no
+    /// clock, as the host times every turn.
+    pub fn start_clock(&mut self, config: ClockConfig, cb_sink: CbSink) {
+        if self.is_client() || self.spectator.is_some() {
+            return;
+        }
+        let mut clock = Clock::new(config);
+        clock.follow(&self.players);
+        self.timer = Some(Timer::start(clock, cb_sink));
+    }
+
+    /// The clock of the game on the board. Practice games are not timed.
+    pub(super) fn game_clock(&self) -> Option<&Clock> {
+        let timer = self.timer.as_ref().filter(|_| self.practice.is_none())?;
+        Some(&timer.clock)
+    }
+
+    fn game_clock_mut(&mut self) -> Option<&mut Clock> {
+        let timer = self.timer.as_mut().filter(|_| self.practice.is_none())?;
+        Some(&mut timer.clock)
+    }
+
+    /// Lets the clock know the turn may have moved on.
+    pub(super) fn follow_clock(&mut self) {
+        if let (Some(timer), None) = (&mut self.timer, &self.practice) {
+            timer.clock.follow(&self.players);
+        }
+        self.sync_ticking();
+    }
+
+    /// Starts the clock over, paused, for the game to come.
+    pub(super) fn reset_clock(&mut self) {
+        if let Some(timer) = &mut self.timer {
+            timer.clock = Clock::new(timer.clock.config);
+        }
+        self.sync_ticking();
+    }
+
+    pub(super) fn toggle_clock(&mut self) -> Result<(), String> {
+        if self.timer.is_some() && self.practice.is_some() {
+            return Err("Practice games are not timed".to_string());
+        }
+        let clock = self
+            .game_clock_mut()
+            .ok_or_else(|| "There is no clock in this game".to_string())?;
+        clock.toggle_pause();
+        self.sync_ticking();
+        Ok(())
+    }
+
+    /// Ticks only while a game clock runs, as there is nothing to redraw otherwise.
+    fn sync_ticking(&self) {
+        if let Some(timer) = &self.timer {
+            let is_running = self.game_clock().is_some_and(|clock| !clock.is_paused());
+            timer.ticking.store(is_running, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Redraws the time left and deals with whoever ran out of it.
+fn tick(siv: &mut Cursive) {
+    let Some((name, action)) = siv
+        .call_on_name("playboard", |play_board: &mut PlayBoard| {
+            play_board.follow_clock();
+            let clock = play_board.game_clock_mut()?;
+            Some((clock.time_out()?, clock.config.on_timeout))
+        })
+        .flatten()
+    else {
+        return;
+    };
+
+    match action {
+        TimeoutAction::Warn => {
+            log_timeout(siv, format!("{name} ran out of time"));
+            siv.add_layer(
+                OnEventView::new(
+                    Dialog::text(format!("Time is up for `{name}`!"))
+                        .title("Clock")
+                        .button("Ok", |s| {
+                            s.pop_layer();
+                        }),
+                )
+                .on_event(Key::Esc, |s| {
+                    s.pop_layer();
+                }),
+            );
+        }
+        TimeoutAction::Log => log_timeout(siv, format!("{name} ran out of time")),
+        TimeoutAction::Scratch => scratch(siv, &name),
+    }
+}
+
+fn log_timeout(siv: &mut Cursive, line: String) {
+    siv.call_on_name("playboard", |play_board: &mut PlayBoard| {
+        play_board.push_log(line);
+    });
+}
+
+/// Scores zero in the first open category of `name`, from the top of the board.
+fn scratch(siv: &mut Cursive, name: &str) {
+    let result = siv
+        .call_on_name("playboard", |play_board: &mut PlayBoard| {
+            let scoreboard = play_board.players.get_player_score(&name.to_string())?;
+            let category = Category::ALL
+                .into_iter()
+                .find(|category| category.is_open(scoreboard))?;
+            play_board.push_log(format!(
+                "{name} ran out of time and scratched {}",
+                category.name()
+            ));
+            Some(play_board.record_score(name, category.input_for(0)))
+        })
+        .flatten();
+
+    match result {
+        Some(Ok(Some(was_over))) => finish_turn(siv, was_over),
+        Some(Err(err)) => show_error(siv, err),
+        _ => {}
+    }
+}
@@ -3,13 +3,27 @@
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
+use unicode_width::UnicodeWidthStr;
 
-const BONUS_LIMIT: u16 = 63;
-const BONUS_SCORE: u16 = 35;
+use crate::bot::Difficulty;
+
+/// Name of the rules implemented here, used to keep records of different rules apart.
+pub const RULE_SET: &str = "Yacht";
+
+/// Widest name a player can have, in terminal columns.
+pub const MAX_NAME_WIDTH: usize = 20;
+
+pub const BONUS_LIMIT: u16 = 63;
+pub const BONUS_SCORE: u16 = 35;
 const SMALL_STRAIGHT_SCORE: u16 = 15;
 const LARGE_STRAIGHT_SCORE: u16 = 30;
 const YACHT_SCORE: u16 = 50;
 
+// Best possible scores for the categories that sum dice
+const CHOICE_MAX: u16 = 30;
+const FULL_HOUSE_MAX: u16 = 28;
+const FOUR_OF_KIND_MAX: u16 = 30;
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Scoreboard {
     pub numbers: [Option<u16>; 6],
@@ -41,7 +55,192 @@ impl Default for Scoreboard {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BonusStatus {
+    Secured,
+    Possible,
+    Impossible,
+}
+
+pub type Dice = [u8; 5];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Category {
+    Ones,
+    Twos,
+    Threes,
+    Fours,
+    Fives,
+    Sixes,
+    Choice,
+    FullHouse,
+    FourOfKind,
+    SmallStraight,
+    LargeStraight,
+    Yacht,
+}
+
+impl Category {
+    pub const ALL: [Self; 12] = [
+        Self::Ones,
+        Self::Twos,
+        Self::Threes,
+        Self::Fours,
+        Self::Fives,
+        Self::Sixes,
+        Self::Choice,
+        Self::FullHouse,
+        Self::FourOfKind,
+        Self::SmallStraight,
+        Self::LargeStraight,
+        Self::Yacht,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Ones => "Ones",
+            Self::Twos => "Twos",
+            Self::Threes => "Threes",
+            Self::Fours => "Fours",
+            Self::Fives => "Fives",
+            Self::Sixes => "Sixes",
+            Self::Choice => "Choice",
+            Self::FullHouse => "Full House",
+            Self::FourOfKind => "Four of a Kind",
+            Self::SmallStraight => "Small Straight",
+            Self::LargeStraight => "Large Straight",
+            Self::Yacht => "* YACHT *",
+        }
+    }
+
+    /// The face counted by Ones ~ Sixes, `None` for the lower section.
+    pub fn face(self) -> Option<u16> {
+        match self {
+            Self::Ones => Some(1),
+            Self::Twos => Some(2),
+            Self::Threes => Some(3),
+            Self::Fours => Some(4),
+            Self::Fives => Some(5),
+            Self::Sixes => Some(6),
+            _ => None,
+        }
+    }
+
+    /// What the scoreboard holds in this category, `None` while it is still open.
+    pub fn value(self, scoreboard: &Scoreboard) -> Option<u16> {
+        match self {
+            Self::Ones => scoreboard.numbers[0],
+            Self::Twos => scoreboard.numbers[1],
+            Self::Threes => scoreboard.numbers[2],
+            Self::Fours => scoreboard.numbers[3],
+            Self::Fives => scoreboard.numbers[4],
+            Self::Sixes => scoreboard.numbers[5],
+            Self::Choice => scoreboard.choice,
+            Self::FullHouse => scoreboard.full_house,
+            Self::FourOfKind => scoreboard.four_of_kind,
+            Self::SmallStraight => scoreboard.small_straight,
+            Self::LargeStraight => scoreboard.large_straight,
+            Self::Yacht => scoreboard.yacht,
+        }
+    }
+
+    #[inline]
+    pub fn is_open(self, scoreboard: &Scoreboard) -> bool {
+        self.value(scoreboard).is_none()
+    }
+
+    /// Score these dice would get in this category.
+    pub fn score(self, dice: &Dice) -> u16 {
+        let mut counts = [0u8; 6];
+        for &die in dice {
+            counts[die as usize - 1] += 1;
+        }
+        let sum = dice.iter().map(|&die| die as u16).sum::<u16>();
+        let has_run = |len: usize| counts.windows(len).any(|run| run.iter().all(|&c| c > 0));
+
+        match self {
+            Self::Choice => sum,
+            Self::FullHouse => {
+                if counts.contains(&3) && counts.contains(&2) {
+                    sum
+                } else {
+                    0
+                }
+            }
+            Self::FourOfKind => {
+                if counts.iter().any(|&c| c >= 4) {
+                    sum
+                } else {
+                    0
+                }
+            }
+            Self::SmallStraight => {
+                if has_run(4) {
+                    SMALL_STRAIGHT_SCORE
+                } else {
+                    0
+                }
+            }
+            Self::LargeStraight => {
+                if has_run(5) {
+                    LARGE_STRAIGHT_SCORE
+                } else {
+                    0
+                }
+            }
+            Self::Yacht => {
+                if counts.contains(&5) {
+                    YACHT_SCORE
+                } else {
+                    0
+                }
+            }
+            upper => {
+                let face = upper.face().unwrap_or(0);
+                face * counts[face as usize - 1] as u16
+            }
+        }
+    }
+
+    /// Whether some dice can score `score` in this category.
+    pub fn is_possible(self, score: u16) -> bool {
+        // There are only 6^5 rolls, few enough to try every one
+        (0..6u16.pow(5)).any(|roll| {
+            let mut dice = [0; 5];
+            let mut rest = roll;
+            for die in &mut dice {
+                *die = (rest % 6) as u8 + 1;
+                rest /= 6;
+            }
+            self.score(&dice) == score
+        })
+    }
+
+    #[inline]
+    pub fn to_score_input(self, dice: &Dice) -> ScoreInput {
+        self.input_for(self.score(dice))
+    }
+
+    /// The input that records `score` in this category.
+    pub fn input_for(self, score: u16) -> ScoreInput {
+        match self {
+            Self::Ones => ScoreInput::Ones(Some(score)),
+            Self::Twos => ScoreInput::Twos(Some(score)),
+            Self::Threes => ScoreInput::Threes(Some(score)),
+            Self::Fours => ScoreInput::Fours(Some(score)),
+            Self::Fives => ScoreInput::Fives(Some(score)),
+            Self::Sixes => ScoreInput::Sixes(Some(score)),
+            Self::Choice => ScoreInput::Choice(Some(score)),
+            Self::FullHouse => ScoreInput::FullHouse(Some(score)),
+            Self::FourOfKind => ScoreInput::FourOfKind(Some(score)),
+            Self::SmallStraight => ScoreInput::SmallStraight(score > 0),
+            Self::LargeStraight => ScoreInput::LargeStraight(score > 0),
+            Self::Yacht => ScoreInput::Yacht(score > 0),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
 pub enum ScoreInput {
     Ones(Option<u16>),
     Twos(Option<u16>),
@@ -57,6 +256,102 @@ pub enum ScoreInput {
     Yacht(bool),
 }
 
+impl Scoreboard {
+    pub fn is_complete(&self) -> bool {
+        self.numbers.iter().all(Option::is_some)
+            && self.choice.is_some()
+            && self.full_house.is_some()
+            && self.four_of_kind.is_some()
+            && self.small_straight.is_some()
+            && self.large_straight.is_some()
+            && self.yacht.is_some()
+    }
+
+    /// Updates the bonus and the total after a category changed.
+    pub fn recalculate(&mut self) {
+        let nums_total = self.numbers.iter().map(|num| num.unwrap_or(0)).sum::<u16>();
+        self.left_to_get_bonus = BONUS_LIMIT.saturating_sub(nums_total);
+        self.bonus = if self.left_to_get_bonus == 0 {
+            BONUS_SCORE
+        } else {
+            0
+        };
+
+        self.total_score = nums_total
+            + self.bonus
+            + self.choice.unwrap_or(0)
+            + self.full_house.unwrap_or(0)
+            + self.four_of_kind.unwrap_or(0)
+            + self.small_straight.unwrap_or(0)
+            + self.large_straight.unwrap_or(0)
+            + self.yacht.unwrap_or(0);
+    }
+
+    #[inline]
+    pub fn filled_count(&self) -> usize {
+        Category::ALL
+            .iter()
+            .filter(|category| !category.is_open(self))
+            .count()
+    }
+
+    /// Points already in Ones ~ Sixes and the most the open boxes among them can still add.
+    fn upper_section(&self) -> (u16, u16) {
+        self.numbers
+            .iter()
+            .zip(1..)
+            .fold((0, 0), |(now, open), (num, face)| match num {
+                Some(num) => (now + num, open),
+                None => (now, open + 5 * face),
+            })
+    }
+
+    pub fn bonus_status(&self) -> BonusStatus {
+        let (upper_now, upper_open) = self.upper_section();
+        if upper_now >= BONUS_LIMIT {
+            BonusStatus::Secured
+        } else if upper_now + upper_open >= BONUS_LIMIT {
+            BonusStatus::Possible
+        } else {
+            BonusStatus::Impossible
+        }
+    }
+
+    /// How far the filled Ones ~ Sixes are above (or below) three of each face.
+    /// Three of each face in every box is exactly `BONUS_LIMIT`.
+    pub fn par_difference(&self) -> i16 {
+        self.numbers
+            .iter()
+            .zip(1..)
+            .filter_map(|(num, face)| num.map(|num| num as i16 - 3 * face))
+            .sum()
+    }
+
+    /// The final score this player would reach by maxing out every open category.
+    pub fn max_achievable(&self) -> u16 {
+        let (upper_now, upper_open) = self.upper_section();
+        let bonus = if self.bonus_status() == BonusStatus::Impossible {
+            0
+        } else {
+            BONUS_SCORE
+        };
+
+        let lower = [
+            (self.choice, CHOICE_MAX),
+            (self.full_house, FULL_HOUSE_MAX),
+            (self.four_of_kind, FOUR_OF_KIND_MAX),
+            (self.small_straight, SMALL_STRAIGHT_SCORE),
+            (self.large_straight, LARGE_STRAIGHT_SCORE),
+            (self.yacht, YACHT_SCORE),
+        ]
+        .iter()
+        .map(|(score, max)| score.unwrap_or(*max))
+        .sum::<u16>();
+
+        upper_now + upper_open + bonus + lower
+    }
+}
+
 impl ScoreInput {
     pub fn inject(self, num: u16) -> Self {
         match self {
@@ -72,19 +367,110 @@ impl ScoreInput {
             _ => self,
         }
     }
+
+    /// The category this input scores in.
+    pub fn category(self) -> Category {
+        match self {
+            Self::Ones(_) => Category::Ones,
+            Self::Twos(_) => Category::Twos,
+            Self::Threes(_) => Category::Threes,
+            Self::Fours(_) => Category::Fours,
+            Self::Fives(_) => Category::Fives,
+            Self::Sixes(_) => Category::Sixes,
+            Self::Choice(_) => Category::Choice,
+            Self::FullHouse(_) => Category::FullHouse,
+            Self::FourOfKind(_) => Category::FourOfKind,
+            Self::SmallStraight(_) => Category::SmallStraight,
+            Self::LargeStraight(_) => Category::LargeStraight,
+            Self::Yacht(_) => Category::Yacht,
+        }
+    }
+
+    /// The points this input records, `None` while no number was given.
+    pub fn points(self) -> Option<u16> {
+        match self {
+            Self::Ones(score)
+            | Self::Twos(score)
+            | Self::Threes(score)
+            | Self::Fours(score)
+            | Self::Fives(score)
+            | Self::Sixes(score)
+            | Self::Choice(score)
+            | Self::FullHouse(score)
+            | Self::FourOfKind(score) => score,
+            Self::SmallStraight(b) => Some(if b { SMALL_STRAIGHT_SCORE } else { 0 }),
+            Self::LargeStraight(b) => Some(if b { LARGE_STRAIGHT_SCORE } else { 0 }),
+            Self::Yacht(b) => Some(if b { YACHT_SCORE } else { 0 }),
+        }
+    }
 }
 
 type PlayerName = String;
 
-#[derive(Serialize, Deserialize)]
+/// Something `Players::undo` can take back.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum HistoryEntry {
+    Score {
+        player: PlayerName,
+        before: Scoreboard,
+    },
+    Rename {
+        from: PlayerName,
+        to: PlayerName,
+    },
+}
+
+/// How the members of a team play.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TeamMode {
+    /// Every member keeps their own card, and the team scores their sum.
+    #[default]
+    Separate,
+    /// The members take turns on one card, kept under the team name.
+    Shared,
+}
+
+impl TeamMode {
+    pub const ALL: [Self; 2] = [Self::Separate, Self::Shared];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Separate => "Own cards, summed",
+            Self::Shared => "One card, taking turns",
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Team {
+    pub name: String,
+    /// Players on the board, or for a shared card, just who rolls in turn.
+    pub members: Vec<PlayerName>,
+    pub mode: TeamMode,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Players {
     players: HashMap<PlayerName, Scoreboard>,
+    /// Seating order. Saves from older versions have none and fall back to name order.
+    #[serde(default)]
+    order: Vec<PlayerName>,
+    #[serde(default)]
+    bots: HashMap<PlayerName, Difficulty>,
+    #[serde(default)]
+    history: Vec<HistoryEntry>,
+    #[serde(default)]
+    teams: Vec<Team>,
 }
 
 impl Players {
     pub fn new() -> Self {
         Self {
             players: HashMap::with_capacity(10),
+            order: Vec::with_capacity(10),
+            bots: HashMap::new(),
+            history: Vec::new(),
+            teams: Vec::new(),
         }
     }
 
@@ -98,24 +484,298 @@ impl Players {
         self.players.get_mut(name)
     }
 
+    /// Adds `name`, leaving out spaces around it.
+    pub fn add_player(&mut self, name: &str) -> Result<(), String> {
+        let name = name.trim();
+        self.check_name(name)?;
+        self.insert_player(name);
+        Ok(())
+    }
+
+    pub fn add_bot(&mut self, name: &str, difficulty: Difficulty) -> Result<(), String> {
+        let name = name.trim();
+        self.add_player(name)?;
+        self.bots.insert(name.to_string(), difficulty);
+        Ok(())
+    }
+
+    /// Gives `old` the name `new`, keeping their scores, seat and history.
+    pub fn rename_player(&mut self, old: &str, new: &str) -> Result<(), String> {
+        let new = new.trim();
+        if old == new {
+            return Ok(());
+        }
+        if !self.players.contains_key(old) {
+            return Err(format!("There is no player named `{old}`"));
+        }
+        self.check_name(new)?;
+
+        self.move_player(old, new);
+        self.history.push(HistoryEntry::Rename {
+            from: old.to_string(),
+            to: new.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Moves everything known about `old` over to `new`.
+    fn move_player(&mut self, old: &str, new: &str) {
+        let Some(scoreboard) = self.players.remove(old) else {
+            return;
+        };
+        self.players.insert(new.to_string(), scoreboard);
+        for player in &mut self.order {
+            if player == old {
+                *player = new.to_string();
+            }
+        }
+        if let Some(difficulty) = self.bots.remove(old) {
+            self.bots.insert(new.to_string(), difficulty);
+        }
+        for entry in &mut self.history {
+            match entry {
+                HistoryEntry::Score { player, .. } if player == old => *player = new.to_string(),
+                HistoryEntry::Rename { to, .. } if to == old => *to = new.to_string(),
+                HistoryEntry::Score { .. } | HistoryEntry::Rename { .. } => {}
+            }
+        }
+        for team in &mut self.teams {
+            match team.mode {
+                TeamMode::Shared if team.name == old => team.name = new.to_string(),
+                TeamMode::Shared => {}
+                TeamMode::Separate => {
+                    for member in &mut team.members {
+                        if member == old {
+                            *member = new.to_string();
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Whether `name` may be given to a new player.
+    fn check_name(&self, name: &str) -> Result<(), String> {
+        if name.trim().is_empty() {
+            Err("A player name cannot be empty".to_string())
+        } else if name.width_cjk() > MAX_NAME_WIDTH {
+            Err(format!(
+                "A player name can be at most {MAX_NAME_WIDTH} columns wide"
+            ))
+        } else if self.players.contains_key(name) {
+            Err(format!("There is already a player named `{name}`"))
+        } else {
+            Ok(())
+        }
+    }
+
     #[inline]
-    pub fn add_player(&mut self, name: &str) {
+    fn insert_player(&mut self, name: &str) {
         self.players.insert(name.to_string(), Scoreboard::default());
+        if !self.order.iter().any(|player| player == name) {
+            self.order.push(name.to_string());
+        }
     }
 
     #[inline]
     pub fn del_player(&mut self, name: &str) -> bool {
+        self.order.retain(|player| player != name);
+        self.bots.remove(name);
+        self.history.retain(|entry| match entry {
+            HistoryEntry::Score { player, .. } => player != name,
+            HistoryEntry::Rename { to, .. } => to != name,
+        });
+        self.teams.retain_mut(|team| match team.mode {
+            TeamMode::Shared => team.name != name,
+            TeamMode::Separate => {
+                team.members.retain(|member| member != name);
+                !team.members.is_empty()
+            }
+        });
         self.players.remove(name).is_some()
     }
 
+    #[inline]
+    pub fn teams(&self) -> &[Team] {
+        &self.teams
+    }
+
+    /// The team `name` plays for, or the shared card `name` is.
+    pub fn team_of(&self, name: &str) -> Option<&Team> {
+        self.teams.iter().find(|team| match team.mode {
+            TeamMode::Shared => team.name == name,
+            TeamMode::Separate => team.members.iter().any(|member| member == name),
+        })
+    }
+
+    pub fn team_total(&self, team: &Team) -> u16 {
+        let total = |name: &str| self.players.get(name).map_or(0, |board| board.total_score);
+        match team.mode {
+            TeamMode::Shared => total(&team.name),
+            TeamMode::Separate => team.members.iter().map(|member| total(member)).sum(),
+        }
+    }
+
+    /// Who rolls next on a shared card: the members take a box each in turn.
+    pub fn next_member<'a>(&self, team: &'a Team) -> Option<&'a str> {
+        let filled = self.players.get(&team.name)?.filled_count();
+        let member = team.members.get(filled % team.members.len().max(1))?;
+        Some(member.as_str())
+    }
+
+    /// Forms a team. Members of a team with its own cards must be on the board
+    /// already and are seated together; a shared card is added as a new column.
+    pub fn add_team(&mut self, name: &str, members: &[&str], mode: TeamMode) -> Result<(), String> {
+        let name = name.trim();
+        if members.len() < 2 {
+            return Err("A team needs at least two members".to_string());
+        }
+        if self.teams.iter().any(|team| team.name == name) {
+            return Err(format!("There is already a team named `{name}`"));
+        }
+        for (i, member) in members.iter().enumerate() {
+            if member.trim().is_empty() {
+                return Err("A member name cannot be empty".to_string());
+            }
+            if members[..i].contains(member) {
+                return Err(format!("`{member}` is listed twice"));
+            }
+        }
+
+        match mode {
+            TeamMode::Shared => {
+                self.check_name(name)?;
+                self.insert_player(name);
+            }
+            TeamMode::Separate => {
+                if name.trim().is_empty() {
+                    return Err("A team name cannot be empty".to_string());
+                }
+                if name.width_cjk() > MAX_NAME_WIDTH {
+                    return Err(format!(
+                        "A team name can be at most {MAX_NAME_WIDTH} columns wide"
+                    ));
+                }
+                for member in members {
+                    if !self.players.contains_key(*member) {
+                        return Err(format!("There is no player named `{member}`"));
+                    }
+                    if let Some(team) = self.team_of(member) {
+                        return Err(format!("`{member}` already plays for `{}`", team.name));
+                    }
+                }
+                self.seat_together(members);
+            }
+        }
+
+        self.teams.push(Team {
+            name: name.to_string(),
+            members: members.iter().map(|member| member.to_string()).collect(),
+            mode,
+        });
+        Ok(())
+    }
+
+    /// Breaks up a team. A shared card leaves the board with it.
+    pub fn del_team(&mut self, name: &str) -> Result<(), String> {
+        let Some(index) = self.teams.iter().position(|team| team.name == name) else {
+            return Err(format!("There is no team named `{name}`"));
+        };
+        if self.teams.remove(index).mode == TeamMode::Shared {
+            self.del_player(name);
+        }
+        Ok(())
+    }
+
+    /// Moves `members` next to each other, at the seat of the first one.
+    fn seat_together(&mut self, members: &[&str]) {
+        let mut order = self
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        let seat = order
+            .iter()
+            .position(|name| members.contains(&name.as_str()))
+            .unwrap_or(order.len());
+        order.retain(|name| !members.contains(&name.as_str()));
+        order.splice(seat..seat, members.iter().map(|member| member.to_string()));
+        self.order = order;
+    }
+
+    #[inline]
+    pub fn bot_difficulty(&self, name: &str) -> Option<Difficulty> {
+        self.bots.get(name).copied()
+    }
+
+    /// Whose turn it is: the first player, in seating order, with the fewest filled boxes.
+    pub fn current_player(&self) -> Option<&'_ PlayerName> {
+        self.iter()
+            .filter(|(_, scoreboard)| !scoreboard.is_complete())
+            .min_by_key(|(_, scoreboard)| scoreboard.filled_count())
+            .map(|(name, _)| name)
+    }
+
     #[inline]
     pub fn is_empty(&self) -> bool {
         self.players.is_empty()
     }
 
+    /// Whether `score` may be recorded for `name`: in an open category, and a
+    /// score some dice can make.
+    pub fn check_score(&self, name: &str, score: ScoreInput) -> Result<(), String> {
+        let scoreboard = self
+            .players
+            .get(name)
+            .ok_or_else(|| format!("There is no player named `{name}`"))?;
+        let category = score.category();
+        if !category.is_open(scoreboard) {
+            return Err(format!("{} is already scored", category.name()));
+        }
+        match score.points() {
+            Some(points) if category.is_possible(points) => Ok(()),
+            Some(points) => Err(format!(
+                "{points} is not a possible score at {}",
+                category.name()
+            )),
+            None => Err(format!("No score was given for {}", category.name())),
+        }
+    }
+
     pub fn calculate_score(&mut self, player_name: &str, score: ScoreInput) -> Option<()> {
         let scoreboard = self.players.get_mut(player_name)?;
 
+        self.history.push(HistoryEntry::Score {
+            player: player_name.to_string(),
+            before: *scoreboard,
+        });
+        Self::apply(scoreboard, score);
+        scoreboard.recalculate();
+
+        Some(())
+    }
+
+    /// Takes back the last recorded change. Returns the player it concerned.
+    pub fn undo(&mut self) -> Result<PlayerName, String> {
+        let nothing = || "There is nothing to undo".to_string();
+        match self.history.pop().ok_or_else(nothing)? {
+            HistoryEntry::Score { player, before } => {
+                *self.players.get_mut(&player).ok_or_else(nothing)? = before;
+                Ok(player)
+            }
+            HistoryEntry::Rename { from, to } => {
+                // Someone may have taken the old name since
+                if self.players.contains_key(&from) {
+                    let err = format!("Cannot rename `{to}` back, `{from}` is taken");
+                    self.history.push(HistoryEntry::Rename { from, to });
+                    return Err(err);
+                }
+                self.move_player(&to, &from);
+                Ok(from)
+            }
+        }
+    }
+
+    fn apply(scoreboard: &mut Scoreboard, score: ScoreInput) {
         match score {
             ScoreInput::Ones(score) => scoreboard.numbers[0] = score,
             ScoreInput::Twos(score) => scoreboard.numbers[1] = score,
@@ -134,38 +794,282 @@ impl Players {
             }
             ScoreInput::Yacht(b) => scoreboard.yacht = Some(if b { YACHT_SCORE } else { 0 }),
         }
+    }
 
-        let nums_total = scoreboard
-            .numbers
-            .iter()
-            .map(|num| num.unwrap_or(0))
-            .sum::<u16>();
-        scoreboard.left_to_get_bonus = BONUS_LIMIT.saturating_sub(nums_total);
-        scoreboard.bonus = if scoreboard.left_to_get_bonus == 0 {
-            BONUS_SCORE
-        } else {
-            0
-        };
+    /// Fills in what `other` knows and this game does not: players and teams missing
+    /// here, and categories still open here. Where both have a different score, ours is
+    /// kept and the conflict is described in the returned list.
+    pub fn merge(&mut self, other: &Players) -> Vec<String> {
+        let mut conflicts = Vec::new();
 
-        scoreboard.total_score = nums_total
-            + scoreboard.bonus
-            + scoreboard.choice.unwrap_or(0)
-            + scoreboard.full_house.unwrap_or(0)
-            + scoreboard.four_of_kind.unwrap_or(0)
-            + scoreboard.small_straight.unwrap_or(0)
-            + scoreboard.large_straight.unwrap_or(0)
-            + scoreboard.yacht.unwrap_or(0);
+        for (name, theirs) in other.iter() {
+            let Some(ours) = self.players.get_mut(name) else {
+                self.insert_player(name);
+                if let Some(difficulty) = other.bot_difficulty(name) {
+                    self.bots.insert(name.clone(), difficulty);
+                }
+                self.players.insert(name.clone(), *theirs);
+                continue;
+            };
 
-        Some(())
+            for category in Category::ALL {
+                match (category.value(ours), category.value(theirs)) {
+                    (None, Some(score)) => Self::apply(ours, category.input_for(score)),
+                    (Some(mine), Some(score)) if mine != score => conflicts.push(format!(
+                        "{name}, {}: kept {mine} over {score}",
+                        category.name()
+                    )),
+                    _ => {}
+                }
+            }
+            ours.recalculate();
+        }
+
+        for team in &other.teams {
+            let is_new = self.teams.iter().all(|ours| ours.name != team.name)
+                && team
+                    .members
+                    .iter()
+                    .all(|member| self.team_of(member).is_none());
+            if is_new {
+                self.teams.push(team.clone());
+            }
+        }
+
+        conflicts
     }
 
     pub fn clear_score(&mut self) {
         for (_, player) in self.players.iter_mut() {
             *player = Scoreboard::default();
         }
+        self.history.clear();
     }
 
+    /// Competition ranking ("1224"): tied players share a rank and the next rank is skipped.
+    pub fn rank_of(&self, name: &str) -> Option<usize> {
+        let total = self.players.get(name)?.total_score;
+        Some(
+            1 + self
+                .players
+                .values()
+                .filter(|scoreboard| scoreboard.total_score > total)
+                .count(),
+        )
+    }
+
+    #[inline]
+    pub fn is_tied(&self, name: &str) -> bool {
+        let Some(scoreboard) = self.players.get(name) else {
+            return false;
+        };
+        self.players
+            .iter()
+            .any(|(other, board)| other != name && board.total_score == scoreboard.total_score)
+    }
+
+    /// A player is eliminated once somebody else already has more points than they can ever reach.
+    pub fn is_eliminated(&self, name: &str) -> bool {
+        let Some(scoreboard) = self.players.get(name) else {
+            return false;
+        };
+        let max_achievable = scoreboard.max_achievable();
+        self.players
+            .iter()
+            .any(|(other, board)| other != name && board.total_score > max_achievable)
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        !self.players.is_empty() && self.players.values().all(Scoreboard::is_complete)
+    }
+
+    /// Players sorted by rank, ties broken by name.
+    pub fn standings(&self) -> Vec<(usize, &'_ PlayerName, &'_ Scoreboard)> {
+        let mut standings = self
+            .players
+            .iter()
+            .map(|(name, scoreboard)| (self.rank_of(name).unwrap_or(0), name, scoreboard))
+            .collect::<Vec<_>>();
+        standings.sort_by(|lhs, rhs| lhs.0.cmp(&rhs.0).then_with(|| lhs.1.cmp(rhs.1)));
+        standings
+    }
+
+    /// Players in seating order.
     pub fn iter(&self) -> impl Iterator<Item = (&'_ PlayerName, &'_ Scoreboard)> {
-        self.players.iter()
+        let mut names = self.players.keys().collect::<Vec<_>>();
+        names.sort_by_key(|name| {
+            let seat = self.order.iter().position(|player| player == *name);
+            (seat.unwrap_or(usize::MAX), *name)
+        });
+        names
+            .into_iter()
+            .map(move |name| (name, &self.players[name]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(players: &Players) -> Vec<&str> {
+        players.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    #[test]
+    fn possible_scores_of_every_category() {
+        let possible = |category: Category| {
+            (0..=60)
+                .filter(|&score| category.is_possible(score))
+                .collect::<Vec<_>>()
+        };
+        let upper = |face: u16| (0..=5).map(|count| count * face).collect::<Vec<_>>();
+
+        for (category, face) in Category::ALL.into_iter().zip(1..=6) {
+            assert_eq!(possible(category), upper(face), "{}", category.name());
+        }
+        assert_eq!(possible(Category::Choice), (5..=30).collect::<Vec<_>>());
+        // 3a + 2b with a != b leaves out 10 and 25
+        let full_house = [0, 7, 8, 9]
+            .into_iter()
+            .chain((11..=24).chain([26, 27, 28]))
+            .collect::<Vec<_>>();
+        assert_eq!(possible(Category::FullHouse), full_house);
+        let four_of_kind = [0].into_iter().chain(5..=30).collect::<Vec<_>>();
+        assert_eq!(possible(Category::FourOfKind), four_of_kind);
+        assert_eq!(possible(Category::SmallStraight), [0, 15]);
+        assert_eq!(possible(Category::LargeStraight), [0, 30]);
+        assert_eq!(possible(Category::Yacht), [0, 50]);
+        assert!(!Category::Ones.is_possible(u16::MAX));
+    }
+
+    #[test]
+    fn undo_walks_back_a_chain_of_renames() {
+        let mut players = Players::new();
+        players.add_player("A").unwrap();
+        players.add_player("Z").unwrap();
+
+        players.rename_player("A", "B").unwrap();
+        players.calculate_score("B", ScoreInput::Choice(Some(20)));
+        players.rename_player("B", "C").unwrap();
+        assert_eq!(names(&players), ["C", "Z"]);
+        assert_eq!(
+            players.get_player_score(&"C".to_string()).unwrap().choice,
+            Some(20)
+        );
+
+        assert_eq!(players.undo().unwrap(), "B");
+        assert_eq!(names(&players), ["B", "Z"]);
+        assert_eq!(players.undo().unwrap(), "B");
+        assert_eq!(
+            players.get_player_score(&"B".to_string()).unwrap().choice,
+            None
+        );
+        assert_eq!(players.undo().unwrap(), "A");
+        assert_eq!(names(&players), ["A", "Z"]);
+        assert!(players.undo().is_err());
+    }
+
+    #[test]
+    fn undo_keeps_a_rename_whose_old_name_is_taken() {
+        let mut players = Players::new();
+        players.add_player("A").unwrap();
+        players.rename_player("A", "B").unwrap();
+        players.add_player("A").unwrap();
+
+        // Adding a player is not in the history, so undo meets the rename first
+        assert!(players.undo().is_err());
+        assert_eq!(names(&players), ["B", "A"]);
+        players.del_player("A");
+        assert_eq!(players.undo().unwrap(), "A");
+    }
+
+    #[test]
+    fn shared_card_members_take_turns() {
+        let mut players = Players::new();
+        players.add_player("Zoe").unwrap();
+        players
+            .add_team("Pair", &["Ann", "Bob"], TeamMode::Shared)
+            .unwrap();
+        assert_eq!(names(&players), ["Zoe", "Pair"]);
+
+        let team = players.team_of("Pair").unwrap().clone();
+        assert_eq!(players.next_member(&team), Some("Ann"));
+        players.calculate_score("Pair", ScoreInput::Choice(Some(20)));
+        assert_eq!(players.next_member(&team), Some("Bob"));
+        players.calculate_score("Pair", ScoreInput::Yacht(true));
+        assert_eq!(players.next_member(&team), Some("Ann"));
+        assert_eq!(players.team_total(&team), 70);
+    }
+
+    #[test]
+    fn a_player_plays_for_one_team() {
+        let mut players = Players::new();
+        for name in ["Ann", "Bob", "Cat"] {
+            players.add_player(name).unwrap();
+        }
+        players
+            .add_team("Red", &["Ann", "Cat"], TeamMode::Separate)
+            .unwrap();
+        // Members are seated together
+        assert_eq!(names(&players), ["Ann", "Cat", "Bob"]);
+
+        assert!(players
+            .add_team("Blue", &["Bob", "Cat"], TeamMode::Separate)
+            .is_err());
+        assert!(players
+            .add_team("Blue", &["Bob", "Bob"], TeamMode::Separate)
+            .is_err());
+        assert!(players
+            .add_team("Blue", &["Bob", "Dan"], TeamMode::Separate)
+            .is_err());
+        assert_eq!(players.teams().len(), 1);
+    }
+
+    #[test]
+    fn teams_follow_renamed_and_deleted_members() {
+        let mut players = Players::new();
+        for name in ["Ann", "Bob", "Cat"] {
+            players.add_player(name).unwrap();
+        }
+        players
+            .add_team("Red", &["Ann", "Bob"], TeamMode::Separate)
+            .unwrap();
+        players.calculate_score("Ann", ScoreInput::Choice(Some(20)));
+        players.calculate_score("Bob", ScoreInput::Choice(Some(10)));
+
+        players.rename_player("Ann", "Amy").unwrap();
+        let team = players.team_of("Amy").unwrap();
+        assert_eq!(team.members, ["Amy", "Bob"]);
+        assert_eq!(players.team_total(team), 30);
+
+        players.del_player("Bob");
+        assert_eq!(players.team_of("Amy").unwrap().members, ["Amy"]);
+        players.del_player("Amy");
+        assert!(players.teams().is_empty());
+
+        players
+            .add_team("Pair", &["Cat", "Dan"], TeamMode::Shared)
+            .unwrap();
+        players.rename_player("Pair", "Duo").unwrap();
+        assert_eq!(players.team_of("Duo").unwrap().members, ["Cat", "Dan"]);
+        players.del_player("Duo");
+        assert!(players.teams().is_empty());
+    }
+
+    #[test]
+    fn names_are_trimmed_unique_and_narrow() {
+        let mut players = Players::new();
+        players.add_player(" Bob ").unwrap();
+        assert_eq!(names(&players), ["Bob"]);
+        assert!(players.add_player("Bob").is_err());
+        assert!(players.add_player("   ").is_err());
+        // Ten wide characters take twenty columns, eleven are too many
+        assert!(players.add_player(&"名".repeat(10)).is_ok());
+        assert!(players.add_player(&"字".repeat(11)).is_err());
+        // Ambiguous characters are as wide as the board draws them
+        assert!(players.add_player(&"α".repeat(11)).is_err());
+        assert!(players
+            .rename_player("Bob", "名".repeat(10).as_str())
+            .is_err());
     }
 }
@@ -0,0 +1,256 @@
+use std::io::{Read, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::players::Players;
+
+/// How a match is decided.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SeriesFormat {
+    /// Whoever wins the most of this many games; over once nobody can catch up.
+    BestOf(usize),
+    /// The highest total over this many games.
+    Total(usize),
+}
+
+impl SeriesFormat {
+    pub fn describe(self) -> String {
+        match self {
+            Self::BestOf(games) => format!("Best of {games}"),
+            Self::Total(games) => format!("Total over {games} games"),
+        }
+    }
+}
+
+/// A match: games played in order by the same roster.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Series {
+    pub format: SeriesFormat,
+    /// Finished games, oldest first. The game on the board is not among them.
+    games: Vec<Players>,
+}
+
+/// Where a player stands in a match.
+pub struct SeriesStanding {
+    pub name: String,
+    /// Their total in every game so far, the one on the board last.
+    pub totals: Vec<Option<u16>>,
+    pub total: u32,
+    pub wins: usize,
+}
+
+impl Series {
+    pub fn new(format: SeriesFormat) -> Self {
+        Self {
+            format,
+            games: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn finished_games(&self) -> usize {
+        self.games.len()
+    }
+
+    /// Files away `current` and returns a fresh game for the same roster.
+    pub fn next_game(&mut self, current: &Players) -> Players {
+        self.games.push(current.clone());
+        let mut next = current.clone();
+        next.clear_score();
+        next
+    }
+
+    /// Everyone in the match, in the seating order of `current` and then
+    /// anyone who has left since.
+    pub fn standings(&self, current: &Players) -> Vec<SeriesStanding> {
+        let mut names = current
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        for game in &self.games {
+            for (name, _) in game.iter() {
+                if !names.contains(name) {
+                    names.push(name.clone());
+                }
+            }
+        }
+
+        let games = self.games.iter().chain([current]).collect::<Vec<_>>();
+        names
+            .into_iter()
+            .map(|name| {
+                let totals = games
+                    .iter()
+                    .map(|game| game.get_player_score(&name).map(|board| board.total_score))
+                    .collect::<Vec<_>>();
+                let wins = self
+                    .decided_games(current)
+                    .filter(|game| game.rank_of(&name) == Some(1))
+                    .count();
+                SeriesStanding {
+                    total: totals.iter().flatten().map(|&total| u32::from(total)).sum(),
+                    totals,
+                    wins,
+                    name,
+                }
+            })
+            .collect()
+    }
+
+    /// Whether the match is decided, counting `current` once it is over.
+    pub fn is_over(&self, current: &Players) -> bool {
+        let played = self.decided_games(current).count();
+        match self.format {
+            SeriesFormat::Total(games) => played >= games,
+            SeriesFormat::BestOf(games) => {
+                let standings = self.standings(current);
+                let mut wins = standings
+                    .iter()
+                    .map(|standing| standing.wins)
+                    .collect::<Vec<_>>();
+                wins.sort_unstable_by(|lhs, rhs| rhs.cmp(lhs));
+                let left = games.saturating_sub(played);
+                match wins.as_slice() {
+                    [] => false,
+                    [_] => played >= games,
+                    [first, second, ..] => played >= games || *first > second + left,
+                }
+            }
+        }
+    }
+
+    /// Who leads the match: the most wins in a best of, the highest total otherwise.
+    pub fn leaders(&self, current: &Players) -> Vec<String> {
+        let standings = self.standings(current);
+        let key = |standing: &SeriesStanding| match self.format {
+            SeriesFormat::BestOf(_) => (standing.wins as u32, standing.total),
+            SeriesFormat::Total(_) => (standing.total, 0),
+        };
+        let Some(best) = standings.iter().map(key).max() else {
+            return Vec::new();
+        };
+        standings
+            .into_iter()
+            .filter(|standing| key(standing) == best)
+            .map(|standing| standing.name)
+            .collect()
+    }
+
+    /// The finished games, and `current` once it is over.
+    fn decided_games<'a>(&'a self, current: &'a Players) -> impl Iterator<Item = &'a Players> {
+        let current = current.is_game_over().then_some(current);
+        self.games.iter().chain(current)
+    }
+}
+
+#[derive(Serialize)]
+struct SeriesFile<'a> {
+    series: &'a Series,
+    current: &'a Players,
+}
+
+/// Reads a save file: a single game, or the game on the board with the match it belongs to.
+pub fn read_save<R: Read>(reader: R) -> Result<(Players, Option<Series>), String> {
+    let mut value: serde_json::Value =
+        serde_json::from_reader(reader).map_err(|err| err.to_string())?;
+    let parse_err = |err: serde_json::Error| err.to_string();
+    match value.get_mut("series").map(serde_json::Value::take) {
+        Some(series) => {
+            let current = value.get_mut("current").map(serde_json::Value::take);
+            Ok((
+                serde_json::from_value(current.unwrap_or_default()).map_err(parse_err)?,
+                Some(serde_json::from_value(series).map_err(parse_err)?),
+            ))
+        }
+        None => Ok((serde_json::from_value(value).map_err(parse_err)?, None)),
+    }
+}
+
+pub fn write_save<W: Write>(
+    writer: W,
+    current: &Players,
+    series: Option<&Series>,
+) -> Result<(), String> {
+    let result = match series {
+        Some(series) => serde_json::to_writer(writer, &SeriesFile { series, current }),
+        None => serde_json::to_writer(writer, current),
+    };
+    result.map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::players::Category;
+
+    /// A finished game where each player scores only their Choice.
+    fn finished_game(choices: &[(&str, u16)]) -> Players {
+        let mut players = Players::new();
+        for (name, _) in choices {
+            players.add_player(name).unwrap();
+        }
+        for (name, choice) in choices {
+            for category in Category::ALL {
+                let score = if category == Category::Choice {
+                    *choice
+                } else {
+                    0
+                };
+                players.calculate_score(name, category.input_for(score));
+            }
+        }
+        assert!(players.is_game_over());
+        players
+    }
+
+    #[test]
+    fn best_of_is_decided_once_nobody_can_catch_up() {
+        let mut series = Series::new(SeriesFormat::BestOf(3));
+        let first = finished_game(&[("Ann", 20), ("Bob", 10)]);
+        assert!(!series.is_over(&first));
+
+        series.next_game(&first);
+        let second = finished_game(&[("Ann", 25), ("Bob", 15)]);
+        assert!(series.is_over(&second));
+        assert_eq!(series.leaders(&second), ["Ann"]);
+    }
+
+    #[test]
+    fn tied_game_is_a_win_for_both() {
+        let mut series = Series::new(SeriesFormat::BestOf(3));
+        let first = finished_game(&[("Ann", 20), ("Bob", 20)]);
+        series.next_game(&first);
+        let second = finished_game(&[("Ann", 25), ("Bob", 15)]);
+
+        let wins = series
+            .standings(&second)
+            .iter()
+            .map(|standing| standing.wins)
+            .collect::<Vec<_>>();
+        assert_eq!(wins, [2, 1]);
+        // Bob can still level it in the last game
+        assert!(!series.is_over(&second));
+    }
+
+    #[test]
+    fn total_ends_after_its_games_even_when_tied() {
+        let mut series = Series::new(SeriesFormat::Total(2));
+        let first = finished_game(&[("Ann", 20), ("Bob", 10)]);
+        series.next_game(&first);
+        let second = finished_game(&[("Ann", 10), ("Bob", 20)]);
+
+        assert!(series.is_over(&second));
+        assert_eq!(series.leaders(&second), ["Ann", "Bob"]);
+    }
+
+    #[test]
+    fn unfinished_game_does_not_count() {
+        let mut series = Series::new(SeriesFormat::Total(2));
+        let first = finished_game(&[("Ann", 20), ("Bob", 10)]);
+        let second = series.next_game(&first);
+
+        assert!(!series.is_over(&second));
+        assert_eq!(series.finished_games(), 1);
+    }
+}
@@ -0,0 +1,107 @@
+use serde::Serialize;
+
+use crate::bot::Difficulty;
+use crate::players::{Category, Players, Scoreboard};
+
+/// The board as other programs see it: players in seating order with what the
+/// board shows next to their scores.
+#[derive(Serialize)]
+pub struct Snapshot<'a> {
+    pub players: Vec<PlayerSnapshot<'a>>,
+    pub current_player: Option<&'a str>,
+    pub game_over: bool,
+}
+
+#[derive(Serialize)]
+pub struct PlayerSnapshot<'a> {
+    pub name: &'a str,
+    pub rank: usize,
+    pub total: u16,
+    pub max_achievable: u16,
+    pub eliminated: bool,
+    pub bot: Option<Difficulty>,
+    pub scoreboard: &'a Scoreboard,
+}
+
+impl<'a> Snapshot<'a> {
+    pub fn of(players: &'a Players) -> Self {
+        Self {
+            players: players
+                .iter()
+                .map(|(name, scoreboard)| PlayerSnapshot {
+                    name,
+                    rank: players.rank_of(name).unwrap_or(0),
+                    total: scoreboard.total_score,
+                    max_achievable: scoreboard.max_achievable(),
+                    eliminated: players.is_eliminated(name),
+                    bot: players.bot_difficulty(name),
+                    scoreboard,
+                })
+                .collect(),
+            current_player: players.current_player().map(String::as_str),
+            game_over: players.is_game_over(),
+        }
+    }
+}
+
+/// The board as a table, one row per category and one column per player.
+pub fn to_csv(players: &Players) -> String {
+    let mut rows = vec![std::iter::once("Category".to_string())
+        .chain(players.iter().map(|(name, _)| csv_field(name)))
+        .collect::<Vec<_>>()];
+    let mut push_row = |label: &str, value: &dyn Fn(&Scoreboard) -> Option<u16>| {
+        rows.push(
+            std::iter::once(csv_field(label))
+                .chain(players.iter().map(|(_, scoreboard)| {
+                    value(scoreboard).map_or_else(String::new, |value| value.to_string())
+                }))
+                .collect(),
+        );
+    };
+
+    for category in Category::ALL {
+        push_row(category.name(), &|scoreboard| category.value(scoreboard));
+        if category == Category::Sixes {
+            push_row("Bonus", &|scoreboard| Some(scoreboard.bonus));
+        }
+    }
+    push_row("Total", &|scoreboard| Some(scoreboard.total_score));
+
+    rows.iter()
+        .map(|row| row.join(",") + "\n")
+        .collect::<String>()
+}
+
+/// Quotes a field holding a comma, a quote or a line break.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::players::ScoreInput;
+
+    #[test]
+    fn csv_has_a_column_per_player() {
+        let mut players = Players::new();
+        players.add_player("Ann").unwrap();
+        players.add_player("Bob, Jr.").unwrap();
+        players.calculate_score("Ann", ScoreInput::Twos(Some(6)));
+        players.calculate_score("Bob, Jr.", ScoreInput::Yacht(true));
+
+        let csv = to_csv(&players);
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "Category,Ann,\"Bob, Jr.\"");
+        assert_eq!(lines[1], "Ones,,");
+        assert_eq!(lines[2], "Twos,6,");
+        assert_eq!(lines[7], "Bonus,0,0");
+        assert_eq!(lines[13], "* YACHT *,,50");
+        assert_eq!(lines[14], "Total,6,50");
+        assert_eq!(lines.len(), 15);
+    }
+}
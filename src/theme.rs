@@ -0,0 +1,143 @@
+use cursive::theme::{BaseColor, Color, Effect, Palette, PaletteColor, Style, Theme};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName {
+    /// The colors of the terminal itself
+    #[default]
+    Terminal,
+    Light,
+    Dark,
+    HighContrast,
+    /// Okabe-Ito colors, told apart with any kind of color blindness
+    Colorblind,
+}
+
+/// How the board marks what is going on in a game.
+#[derive(Clone, Copy)]
+pub struct BoardStyles {
+    /// A category not scored yet
+    pub empty: Style,
+    /// A category scored as zero
+    pub scratched: Style,
+    /// The upper section bonus, once it is secured
+    pub bonus: Style,
+    /// Out of reach, like a lost bonus or an eliminated player
+    pub out_of_reach: Style,
+    pub leader: Style,
+    pub current: Style,
+}
+
+impl ThemeName {
+    pub const ALL: [Self; 5] = [
+        Self::Terminal,
+        Self::Light,
+        Self::Dark,
+        Self::HighContrast,
+        Self::Colorblind,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Terminal => "terminal",
+            Self::Light => "light",
+            Self::Dark => "dark",
+            Self::HighContrast => "high-contrast",
+            Self::Colorblind => "colorblind",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|theme| theme.name() == name)
+    }
+
+    pub fn theme(self) -> Theme {
+        let mut palette = Palette::terminal_default();
+        match self {
+            Self::Terminal | Self::Colorblind => {}
+            Self::Light => {
+                palette[PaletteColor::Background] = Color::Light(BaseColor::White);
+                palette[PaletteColor::View] = Color::Light(BaseColor::White);
+                palette[PaletteColor::Primary] = Color::Dark(BaseColor::Black);
+                palette[PaletteColor::Secondary] = Color::Dark(BaseColor::Blue);
+                palette[PaletteColor::Tertiary] = Color::Dark(BaseColor::White);
+                palette[PaletteColor::TitlePrimary] = Color::Dark(BaseColor::Blue);
+                palette[PaletteColor::TitleSecondary] = Color::Dark(BaseColor::Cyan);
+                palette[PaletteColor::Highlight] = Color::Dark(BaseColor::Blue);
+                palette[PaletteColor::HighlightInactive] = Color::Dark(BaseColor::White);
+                palette[PaletteColor::HighlightText] = Color::Light(BaseColor::White);
+            }
+            Self::Dark => {
+                palette[PaletteColor::Background] = Color::Dark(BaseColor::Black);
+                palette[PaletteColor::View] = Color::Dark(BaseColor::Black);
+                palette[PaletteColor::Primary] = Color::Dark(BaseColor::White);
+                palette[PaletteColor::Secondary] = Color::Light(BaseColor::Blue);
+                palette[PaletteColor::Tertiary] = Color::Light(BaseColor::Black);
+                palette[PaletteColor::TitlePrimary] = Color::Light(BaseColor::Yellow);
+                palette[PaletteColor::TitleSecondary] = Color::Light(BaseColor::Cyan);
+                palette[PaletteColor::Highlight] = Color::Dark(BaseColor::Cyan);
+                palette[PaletteColor::HighlightInactive] = Color::Dark(BaseColor::Blue);
+                palette[PaletteColor::HighlightText] = Color::Dark(BaseColor::Black);
+            }
+            Self::HighContrast => {
+                palette[PaletteColor::Background] = Color::Dark(BaseColor::Black);
+                palette[PaletteColor::View] = Color::Dark(BaseColor::Black);
+                palette[PaletteColor::Primary] = Color::Light(BaseColor::White);
+                palette[PaletteColor::Secondary] = Color::Light(BaseColor::White);
+                palette[PaletteColor::Tertiary] = Color::Light(BaseColor::White);
+                palette[PaletteColor::TitlePrimary] = Color::Light(BaseColor::Yellow);
+                palette[PaletteColor::TitleSecondary] = Color::Light(BaseColor::Yellow);
+                palette[PaletteColor::Highlight] = Color::Light(BaseColor::Yellow);
+                palette[PaletteColor::HighlightInactive] = Color::Light(BaseColor::White);
+                palette[PaletteColor::HighlightText] = Color::Dark(BaseColor::Black);
+            }
+        }
+
+        Theme {
+            shadow: false,
+            palette,
+            ..Default::default()
+        }
+    }
+
+    pub fn board_styles(self) -> BoardStyles {
+        let leader = Style::from(Effect::Reverse);
+        let current = Style::from(Effect::Underline);
+
+        match self {
+            Self::Terminal | Self::Light | Self::Dark => BoardStyles {
+                empty: Effect::Dim.into(),
+                scratched: Color::Dark(BaseColor::Red).into(),
+                bonus: Color::Dark(BaseColor::Green).into(),
+                out_of_reach: Effect::Dim.into(),
+                leader,
+                // Yellow is hard to read on white
+                current: current.combine(Color::Dark(if self == Self::Light {
+                    BaseColor::Blue
+                } else {
+                    BaseColor::Yellow
+                })),
+            },
+            Self::HighContrast => BoardStyles {
+                empty: Style::none(),
+                scratched: Style::from(Color::Light(BaseColor::Red)).combine(Effect::Bold),
+                bonus: Style::from(Color::Light(BaseColor::Green)).combine(Effect::Bold),
+                out_of_reach: Effect::Italic.into(),
+                leader: leader.combine(Effect::Bold),
+                current: current
+                    .combine(Effect::Bold)
+                    .combine(Color::Light(BaseColor::Yellow)),
+            },
+            Self::Colorblind => BoardStyles {
+                empty: Effect::Dim.into(),
+                // Vermillion, bluish green and orange
+                scratched: Color::Rgb(213, 94, 0).into(),
+                bonus: Color::Rgb(0, 158, 115).into(),
+                out_of_reach: Effect::Dim.into(),
+                leader,
+                current: current.combine(Color::Rgb(230, 159, 0)),
+            },
+        }
+    }
+}
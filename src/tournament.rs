@@ -0,0 +1,437 @@
+use std::fs;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::players::Players;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TournamentFormat {
+    /// Winners go through until one is left; an odd one out gets a bye.
+    SingleElimination,
+    /// Everyone plays everyone else in their group once.
+    RoundRobin { group_size: usize },
+}
+
+impl TournamentFormat {
+    pub fn describe(self) -> String {
+        match self {
+            Self::SingleElimination => "Single elimination".to_string(),
+            Self::RoundRobin { group_size } => format!("Round robin, groups of {group_size}"),
+        }
+    }
+}
+
+/// One game of the tournament, or a bye when there is no one to play.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Pairing {
+    pub home: String,
+    pub away: Option<String>,
+    /// The round-robin group, 0 in an elimination bracket.
+    pub group: usize,
+    /// Totals of `home` and `away`, once played.
+    pub result: Option<[u16; 2]>,
+}
+
+impl Pairing {
+    /// The winner, once there is one. A bye goes through without playing.
+    pub fn winner(&self) -> Option<&str> {
+        let Some(away) = &self.away else {
+            return Some(&self.home);
+        };
+        match self.result? {
+            [home, away_total] if home > away_total => Some(&self.home),
+            [home, away_total] if away_total > home => Some(away),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn is_pending(&self) -> bool {
+        self.away.is_some() && self.result.is_none()
+    }
+
+    pub fn describe(&self) -> String {
+        match (&self.away, self.result) {
+            (None, _) => format!("{} (bye)", self.home),
+            (Some(away), None) => format!("{} vs {away}", self.home),
+            (Some(away), Some([home_total, away_total])) => {
+                format!("{} {home_total} : {away_total} {away}", self.home)
+            }
+        }
+    }
+}
+
+/// Where an entrant stands in their round-robin group.
+pub struct GroupStanding<'a> {
+    pub name: &'a str,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    /// Points scored in every game played
+    pub total: u32,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Tournament {
+    pub format: TournamentFormat,
+    pub entrants: Vec<String>,
+    /// Every round so far. A round-robin schedule is drawn up in full at the start.
+    pub rounds: Vec<Vec<Pairing>>,
+}
+
+impl Tournament {
+    pub fn new(format: TournamentFormat, entrants: Vec<String>) -> Result<Self, String> {
+        if entrants.len() < 2 {
+            return Err("A tournament needs at least two entrants".to_string());
+        }
+        // Every pairing is played as a game, so entrants need valid player names
+        let mut names = Players::new();
+        for entrant in &entrants {
+            names.add_player(entrant)?;
+        }
+        if let TournamentFormat::RoundRobin { group_size } = format {
+            if group_size < 2 {
+                return Err("A group needs at least two entrants".to_string());
+            }
+        }
+
+        let rounds = match format {
+            TournamentFormat::SingleElimination => vec![pair_up(&entrants)],
+            TournamentFormat::RoundRobin { group_size } => round_robin(&entrants, group_size),
+        };
+        Ok(Self {
+            format,
+            entrants,
+            rounds,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let file = fs::File::open(path).map_err(|err| err.to_string())?;
+        serde_json::from_reader(BufReader::new(file)).map_err(|err| err.to_string())
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let file = fs::File::create(path).map_err(|err| err.to_string())?;
+        serde_json::to_writer(BufWriter::new(file), self).map_err(|err| err.to_string())
+    }
+
+    /// Games that can be played now, as `(round, index)`.
+    pub fn pending(&self) -> Vec<(usize, usize)> {
+        self.rounds
+            .iter()
+            .enumerate()
+            .flat_map(|(round, pairings)| {
+                pairings
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, pairing)| pairing.is_pending())
+                    .map(move |(index, _)| (round, index))
+            })
+            .collect()
+    }
+
+    #[inline]
+    pub fn pairing(&self, round: usize, index: usize) -> Option<&Pairing> {
+        self.rounds.get(round)?.get(index)
+    }
+
+    /// A fresh game between the two sides of a pairing.
+    pub fn game_for(&self, round: usize, index: usize) -> Result<Players, String> {
+        let pairing = self
+            .pairing(round, index)
+            .filter(|pairing| pairing.is_pending())
+            .ok_or_else(|| "That game is not waiting to be played".to_string())?;
+        let mut players = Players::new();
+        players.add_player(&pairing.home)?;
+        if let Some(away) = &pairing.away {
+            players.add_player(away)?;
+        }
+        Ok(players)
+    }
+
+    /// Takes the totals from a finished game. In a bracket, a full round draws
+    /// up the next one; a tie there has to be played again.
+    pub fn record(&mut self, round: usize, index: usize, game: &Players) -> Result<(), String> {
+        let format = self.format;
+        let pairing = self
+            .rounds
+            .get_mut(round)
+            .and_then(|pairings| pairings.get_mut(index))
+            .filter(|pairing| pairing.is_pending())
+            .ok_or_else(|| "That game is not waiting to be played".to_string())?;
+        let Some(away) = &pairing.away else {
+            return Err("A bye is not played".to_string());
+        };
+
+        let total = |name: &String| {
+            game.get_player_score(name)
+                .map(|scoreboard| scoreboard.total_score)
+                .ok_or_else(|| format!("`{name}` is not in this game"))
+        };
+        let result = [total(&pairing.home)?, total(away)?];
+        if format == TournamentFormat::SingleElimination && result[0] == result[1] {
+            return Err(format!(
+                "{} ended in a tie and is played again",
+                pairing.describe()
+            ));
+        }
+        pairing.result = Some(result);
+
+        if format == TournamentFormat::SingleElimination {
+            self.advance();
+        }
+        Ok(())
+    }
+
+    /// Draws up the next bracket round once every game of the last one has a winner.
+    fn advance(&mut self) {
+        let Some(last) = self.rounds.last() else {
+            return;
+        };
+        // Whoever had a bye plays first, so that the same one does not get it again
+        let Some(mut winners) = last
+            .iter()
+            .map(|pairing| Some((pairing.away.is_some(), pairing.winner()?.to_string())))
+            .collect::<Option<Vec<_>>>()
+        else {
+            return;
+        };
+        if winners.len() > 1 {
+            winners.sort_by_key(|(has_played, _)| *has_played);
+            let winners = winners
+                .into_iter()
+                .map(|(_, name)| name)
+                .collect::<Vec<_>>();
+            self.rounds.push(pair_up(&winners));
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.pending().is_empty()
+    }
+
+    /// The champion of a finished bracket.
+    pub fn champion(&self) -> Option<&str> {
+        match self.rounds.last()?.as_slice() {
+            [final_game] if self.format == TournamentFormat::SingleElimination => {
+                final_game.winner()
+            }
+            _ => None,
+        }
+    }
+
+    /// Each round-robin group, best first: by wins, then draws, then points.
+    pub fn group_standings(&self) -> Vec<Vec<GroupStanding<'_>>> {
+        let TournamentFormat::RoundRobin { group_size } = self.format else {
+            return Vec::new();
+        };
+
+        let mut groups = groups(&self.entrants, group_size)
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|name| GroupStanding {
+                        name,
+                        wins: 0,
+                        draws: 0,
+                        losses: 0,
+                        total: 0,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        for pairing in self.rounds.iter().flatten() {
+            let (Some(away), Some(result)) = (&pairing.away, pairing.result) else {
+                continue;
+            };
+            let Some(group) = groups.get_mut(pairing.group) else {
+                continue;
+            };
+            for (name, own, other) in [
+                (&pairing.home, result[0], result[1]),
+                (away, result[1], result[0]),
+            ] {
+                let Some(standing) = group.iter_mut().find(|standing| standing.name == name) else {
+                    continue;
+                };
+                standing.total += u32::from(own);
+                match own.cmp(&other) {
+                    std::cmp::Ordering::Greater => standing.wins += 1,
+                    std::cmp::Ordering::Equal => standing.draws += 1,
+                    std::cmp::Ordering::Less => standing.losses += 1,
+                }
+            }
+        }
+
+        for group in &mut groups {
+            group.sort_by(|lhs, rhs| {
+                (rhs.wins, rhs.draws, rhs.total).cmp(&(lhs.wins, lhs.draws, lhs.total))
+            });
+        }
+        groups
+    }
+}
+
+/// Pairs entrants in order; the last one gets a bye when they are odd in number.
+fn pair_up(entrants: &[String]) -> Vec<Pairing> {
+    entrants
+        .chunks(2)
+        .map(|pair| Pairing {
+            home: pair[0].clone(),
+            away: pair.get(1).cloned(),
+            group: 0,
+            result: None,
+        })
+        .collect()
+}
+
+/// Splits the entrants in order into groups of at most `group_size`, as even in size as can be.
+fn groups(entrants: &[String], group_size: usize) -> std::slice::Chunks<'_, String> {
+    let count = entrants.len().div_ceil(group_size.max(1));
+    entrants.chunks(entrants.len().div_ceil(count.max(1)).max(1))
+}
+
+/// Schedules each group with the circle method, so that nobody plays twice in a round.
+fn round_robin(entrants: &[String], group_size: usize) -> Vec<Vec<Pairing>> {
+    let mut rounds: Vec<Vec<Pairing>> = Vec::new();
+
+    for (group, members) in groups(entrants, group_size).enumerate() {
+        let mut seats = members.iter().map(Some).collect::<Vec<_>>();
+        if seats.len() % 2 == 1 {
+            seats.push(None);
+        }
+
+        for round in 0..seats.len() - 1 {
+            if rounds.len() <= round {
+                rounds.push(Vec::new());
+            }
+            for i in 0..seats.len() / 2 {
+                let (Some(home), Some(away)) = (seats[i], seats[seats.len() - 1 - i]) else {
+                    continue;
+                };
+                rounds[round].push(Pairing {
+                    home: home.clone(),
+                    away: Some(away.clone()),
+                    group,
+                    result: None,
+                });
+            }
+            // The first seat stays put while everyone else moves one along
+            seats[1..].rotate_right(1);
+        }
+    }
+
+    rounds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::players::Category;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    /// Plays the pairing at `(round, index)` with the given Choice scores.
+    fn play(tournament: &mut Tournament, round: usize, index: usize, totals: [u16; 2]) {
+        let mut game = tournament.game_for(round, index).unwrap();
+        let pairing = tournament.pairing(round, index).unwrap().clone();
+        let sides = [Some(pairing.home), pairing.away];
+        for (name, total) in sides.iter().flatten().zip(totals) {
+            game.calculate_score(name, Category::Choice.input_for(total));
+        }
+        tournament.record(round, index, &game).unwrap();
+    }
+
+    fn bye(round: &[Pairing]) -> Option<&str> {
+        round
+            .iter()
+            .find(|pairing| pairing.away.is_none())
+            .map(|pairing| pairing.home.as_str())
+    }
+
+    #[test]
+    fn nobody_plays_twice_in_a_round() {
+        let entrants = names(&["A", "B", "C", "D", "E", "F", "G", "H", "I"]);
+        let rounds = round_robin(&entrants, 5);
+
+        for round in &rounds {
+            let mut seen = Vec::new();
+            for pairing in round {
+                for name in [Some(&pairing.home), pairing.away.as_ref()]
+                    .into_iter()
+                    .flatten()
+                {
+                    assert!(!seen.contains(name), "{name} plays twice in a round");
+                    seen.push(name.clone());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn everyone_meets_their_group_once() {
+        let entrants = names(&["A", "B", "C", "D", "E", "F", "G"]);
+        let rounds = round_robin(&entrants, 4);
+
+        for group in groups(&entrants, 4) {
+            for (i, lhs) in group.iter().enumerate() {
+                for rhs in &group[i + 1..] {
+                    let meetings = rounds
+                        .iter()
+                        .flatten()
+                        .filter(|pairing| {
+                            let away = pairing.away.as_ref();
+                            (&pairing.home == lhs && away == Some(rhs))
+                                || (&pairing.home == rhs && away == Some(lhs))
+                        })
+                        .count();
+                    assert_eq!(meetings, 1, "{lhs} and {rhs}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn byes_rotate() {
+        let entrants = names(&["A", "B", "C", "D", "E"]);
+        let mut tournament =
+            Tournament::new(TournamentFormat::SingleElimination, entrants).unwrap();
+        assert_eq!(bye(&tournament.rounds[0]), Some("E"));
+
+        play(&mut tournament, 0, 0, [20, 10]);
+        play(&mut tournament, 0, 1, [20, 10]);
+        assert_eq!(tournament.rounds.len(), 2);
+        let second_bye = bye(&tournament.rounds[1]).map(str::to_string);
+        assert!(second_bye.is_some());
+        assert_ne!(second_bye.as_deref(), Some("E"));
+
+        play(&mut tournament, 1, 0, [20, 10]);
+        assert_eq!(tournament.rounds.len(), 3);
+        assert_eq!(bye(&tournament.rounds[2]), None);
+
+        play(&mut tournament, 2, 0, [10, 20]);
+        assert!(tournament.is_finished());
+        assert!(tournament.champion().is_some());
+    }
+
+    #[test]
+    fn tie_in_a_bracket_is_played_again() {
+        let entrants = names(&["A", "B"]);
+        let mut tournament =
+            Tournament::new(TournamentFormat::SingleElimination, entrants).unwrap();
+
+        let mut game = tournament.game_for(0, 0).unwrap();
+        for name in ["A", "B"] {
+            game.calculate_score(name, Category::Choice.input_for(15));
+        }
+        assert!(tournament.record(0, 0, &game).is_err());
+        assert_eq!(tournament.pending(), [(0, 0)]);
+    }
+}
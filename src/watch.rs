@@ -0,0 +1,55 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use cursive::{CbSink, Cursive};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Polls a file and runs `on_change` on the UI thread whenever it is modified.
+/// The polling stops when the watcher is dropped.
+pub struct FileWatcher {
+    stop: Arc<AtomicBool>,
+}
+
+impl FileWatcher {
+    pub fn start(path: PathBuf, cb_sink: CbSink, on_change: fn(&mut Cursive)) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let should_stop = Arc::clone(&stop);
+        thread::spawn(move || {
+            let mut last_stamp = stamp(&path);
+            loop {
+                thread::sleep(POLL_INTERVAL);
+                if should_stop.load(Ordering::Relaxed) {
+                    return;
+                }
+
+                let stamp = stamp(&path);
+                if stamp != last_stamp {
+                    last_stamp = stamp;
+                    if cb_sink.send(Box::new(on_change)).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        Self { stop }
+    }
+}
+
+impl Drop for FileWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Modification time and length, so that writes within the same timestamp are noticed too.
+fn stamp(path: &PathBuf) -> Option<(SystemTime, u64)> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}